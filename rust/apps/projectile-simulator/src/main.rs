@@ -16,8 +16,8 @@ struct Environment {
 }
 
 fn tick(env: &Environment, proj: &Projectile) -> Projectile {
-    let position = proj.position.add_vector(&proj.velocity);
-    let velocity = proj.velocity.add(&env.gravity).add(&env.wind);
+    let position = &proj.position + &proj.velocity;
+    let velocity = &proj.velocity + &env.gravity + &env.wind;
     Projectile { position, velocity }
 }
 
@@ -51,9 +51,7 @@ fn main() {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use core::tuple::CoordValue;
-
-    const EPSILON: CoordValue = 0.0001;
+    use core::assert_approx_eq;
 
     #[test]
     fn test_tick() {
@@ -66,11 +64,7 @@ mod tests {
             velocity: Vector::new(1.0, 1.8, 0.0),
         };
         let proj = tick(&env, &proj);
-        assert!((proj.position.x() - 1.0).abs() < EPSILON);
-        assert!((proj.position.y() - 2.8).abs() < EPSILON);
-        assert!((proj.position.z() - 0.0).abs() < EPSILON);
-        assert!((proj.velocity.x() - 0.99).abs() < EPSILON);
-        assert!((proj.velocity.y() - 1.7).abs() < EPSILON);
-        assert!((proj.velocity.z() - 0.0).abs() < EPSILON);
+        assert_approx_eq!(proj.position, Point::new(1.0, 2.8, 0.0));
+        assert_approx_eq!(proj.velocity, Vector::new(0.99, 1.7, 0.0));
     }
 }
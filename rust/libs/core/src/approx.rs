@@ -0,0 +1,230 @@
+use crate::color::Color;
+use crate::fixed_matrix::FixedMatrix;
+use crate::matrix::Matrix;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::tuple::{widen, CoordValue, Tuple};
+use crate::vector::Vector;
+
+/// Tolerance used by [`ApproxEq::approx_eq_default`] and the assertion macros.
+#[cfg(not(feature = "f64"))]
+pub const EPSILON: CoordValue = 0.0001;
+#[cfg(feature = "f64")]
+pub const EPSILON: CoordValue = 0.00001;
+
+/// Equality up to a tolerance, for values that went through floating point
+/// arithmetic and can't be compared exactly.
+pub trait ApproxEq<Rhs: ?Sized = Self> {
+    fn approx_eq(&self, other: &Rhs, eps: CoordValue) -> bool;
+
+    fn approx_eq_default(&self, other: &Rhs) -> bool {
+        self.approx_eq(other, EPSILON)
+    }
+}
+
+impl ApproxEq for f32 {
+    fn approx_eq(&self, other: &f32, eps: CoordValue) -> bool {
+        ((self - other).abs() as CoordValue) < eps
+    }
+}
+
+// Compared in f64, so that matrix entries are not rounded to `CoordValue`
+impl ApproxEq for f64 {
+    fn approx_eq(&self, other: &f64, eps: CoordValue) -> bool {
+        (self - other).abs() < widen(eps)
+    }
+}
+
+impl ApproxEq for Tuple {
+    fn approx_eq(&self, other: &Tuple, eps: CoordValue) -> bool {
+        self.x.approx_eq(&other.x, eps)
+            && self.y.approx_eq(&other.y, eps)
+            && self.z.approx_eq(&other.z, eps)
+            && self.w.approx_eq(&other.w, eps)
+    }
+}
+
+impl ApproxEq for Point {
+    fn approx_eq(&self, other: &Point, eps: CoordValue) -> bool {
+        self.as_tuple().approx_eq(&other.as_tuple(), eps)
+    }
+}
+
+impl ApproxEq for Vector {
+    fn approx_eq(&self, other: &Vector, eps: CoordValue) -> bool {
+        self.as_tuple().approx_eq(&other.as_tuple(), eps)
+    }
+}
+
+impl ApproxEq for Normal {
+    fn approx_eq(&self, other: &Normal, eps: CoordValue) -> bool {
+        self.as_tuple().approx_eq(&other.as_tuple(), eps)
+    }
+}
+
+impl ApproxEq for Color {
+    fn approx_eq(&self, other: &Color, eps: CoordValue) -> bool {
+        self.as_tuple().approx_eq(&other.as_tuple(), eps)
+    }
+}
+
+impl ApproxEq for Matrix {
+    fn approx_eq(&self, other: &Matrix, eps: CoordValue) -> bool {
+        self.rows() == other.rows()
+            && self.columns() == other.columns()
+            && (0..self.rows()).all(|i| {
+                (0..self.columns()).all(|j| self.get(i, j).approx_eq(&other.get(i, j), eps))
+            })
+    }
+}
+
+impl<const R: usize, const C: usize> ApproxEq for FixedMatrix<R, C> {
+    fn approx_eq(&self, other: &FixedMatrix<R, C>, eps: CoordValue) -> bool {
+        (0..R).all(|i| (0..C).all(|j| self.get(i, j).approx_eq(&other.get(i, j), eps)))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for [T] {
+    fn approx_eq(&self, other: &[T], eps: CoordValue) -> bool {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a.approx_eq(b, eps))
+    }
+}
+
+impl<T: ApproxEq> ApproxEq for Vec<T> {
+    fn approx_eq(&self, other: &Vec<T>, eps: CoordValue) -> bool {
+        self.as_slice().approx_eq(other.as_slice(), eps)
+    }
+}
+
+/// Asserts that two values are equal up to a tolerance, which defaults to
+/// [`approx::EPSILON`](crate::approx::EPSILON).
+#[macro_export]
+macro_rules! assert_approx_eq {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_approx_eq!($left, $right, $crate::approx::EPSILON)
+    };
+    ($left:expr, $right:expr, $eps:expr $(,)?) => {
+        match (&$left, &$right, $eps) {
+            (left, right, eps) => {
+                if !$crate::approx::ApproxEq::approx_eq(left, right, eps) {
+                    panic!(
+                        "assertion `left ≈ right` failed\n  left: {:?}\n right: {:?}\n   eps: {:?}",
+                        left, right, eps
+                    );
+                }
+            }
+        }
+    };
+}
+
+/// Asserts that two values differ by more than a tolerance, which defaults to
+/// [`approx::EPSILON`](crate::approx::EPSILON).
+#[macro_export]
+macro_rules! assert_approx_ne {
+    ($left:expr, $right:expr $(,)?) => {
+        $crate::assert_approx_ne!($left, $right, $crate::approx::EPSILON)
+    };
+    ($left:expr, $right:expr, $eps:expr $(,)?) => {
+        match (&$left, &$right, $eps) {
+            (left, right, eps) => {
+                if $crate::approx::ApproxEq::approx_eq(left, right, eps) {
+                    panic!(
+                        "assertion `left ≉ right` failed\n  left: {:?}\n right: {:?}\n   eps: {:?}",
+                        left, right, eps
+                    );
+                }
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NEAR: CoordValue = EPSILON / 2.0;
+    const FAR: CoordValue = EPSILON * 10.0;
+
+    #[test]
+    fn test_scalars() {
+        let one: CoordValue = 1.0;
+        assert!(one.approx_eq(&(1.0 + NEAR), EPSILON));
+        assert!(!one.approx_eq(&(1.0 + FAR), EPSILON));
+    }
+
+    #[test]
+    fn test_f64_compares_in_f64() {
+        let eps = widen(EPSILON);
+        assert!(0.0_f64.approx_eq(&(eps * (1.0 - 1e-9)), EPSILON));
+        assert!(!0.0_f64.approx_eq(&eps, EPSILON));
+    }
+
+    #[test]
+    fn test_custom_epsilon() {
+        assert!(1.0_f32.approx_eq(&1.05, 0.1));
+        assert!(!1.0_f32.approx_eq(&1.05, 0.01));
+    }
+
+    #[test]
+    fn test_default_epsilon() {
+        assert!(Vector::new(1.0, 2.0, 3.0).approx_eq_default(&Vector::new(1.0 + NEAR, 2.0, 3.0)));
+        assert!(!Vector::new(1.0, 2.0, 3.0).approx_eq_default(&Vector::new(1.0 + FAR, 2.0, 3.0)));
+    }
+
+    #[test]
+    fn test_tuples() {
+        let t = Tuple::homogeneous(1.0, 2.0, 3.0, 1.0);
+        assert!(t.approx_eq(
+            &Tuple::homogeneous(1.0 + NEAR, 2.0 - NEAR, 3.0, 1.0),
+            EPSILON
+        ));
+        assert!(!t.approx_eq(&Tuple::homogeneous(1.0, 2.0, 3.0, 0.0), EPSILON));
+    }
+
+    #[test]
+    fn test_points_vectors_and_colors() {
+        assert_approx_eq!(Point::new(1.0, 2.0, 3.0), Point::new(1.0, 2.0 + NEAR, 3.0));
+        assert_approx_eq!(
+            Vector::new(1.0, 2.0, 3.0),
+            Vector::new(1.0, 2.0, 3.0 - NEAR)
+        );
+        assert_approx_eq!(Color::new(0.1, 0.2, 0.3), Color::new(0.1 + NEAR, 0.2, 0.3));
+        assert_approx_ne!(Color::new(0.1, 0.2, 0.3), Color::new(0.1 + FAR, 0.2, 0.3));
+    }
+
+    #[test]
+    fn test_matrices() {
+        let a = Matrix::from_string("1 2\n3 4");
+        let near = widen(NEAR);
+        let b = Matrix::from_vec(vec![vec![1.0 + near, 2.0], vec![3.0, 4.0 - near]]);
+        assert_approx_eq!(a, b);
+        assert_approx_ne!(a, Matrix::from_string("1 2 0\n3 4 0"));
+        assert_approx_ne!(a, Matrix::from_string("1 2\n3 5"));
+    }
+
+    #[test]
+    fn test_fixed_matrices() {
+        let (near, far) = (widen(NEAR), widen(FAR));
+        let a = FixedMatrix::from_array([[1.0, 2.0], [3.0, 4.0]]);
+        assert_approx_eq!(a, FixedMatrix::from_array([[1.0, 2.0 + near], [3.0, 4.0]]));
+        assert_approx_ne!(a, FixedMatrix::from_array([[1.0, 2.0 + far], [3.0, 4.0]]));
+    }
+
+    #[test]
+    fn test_vecs() {
+        assert_approx_eq!(vec![1.0, 2.0], vec![1.0 + NEAR, 2.0]);
+        assert_approx_ne!(vec![1.0, 2.0], vec![1.0]);
+    }
+
+    #[test]
+    fn test_macro_with_explicit_epsilon() {
+        assert_approx_eq!(1.0_f32, 1.05, 0.1);
+        assert_approx_ne!(1.0_f32, 1.05, 0.01);
+    }
+
+    #[test]
+    #[should_panic(expected = "assertion `left ≈ right` failed")]
+    fn test_macro_panics_when_not_approx_eq() {
+        assert_approx_eq!(Point::new(1.0, 2.0, 3.0), Point::new(1.0, 2.1, 3.0));
+    }
+}
@@ -0,0 +1,409 @@
+use crate::fixed_matrix::Matrix4;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::Ray;
+use crate::tuple::CoordValue;
+
+/// An axis-aligned bounding box, from its `min` corner to its `max` corner.
+///
+/// The empty box has its corners at +∞ and -∞ so that adding anything to it
+/// gives that thing's bounds. Unbounded shapes, such as planes, have infinite
+/// coordinates.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Bounds {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl Bounds {
+    pub fn new(min: Point, max: Point) -> Bounds {
+        Bounds { min, max }
+    }
+
+    pub fn empty() -> Bounds {
+        let inf = CoordValue::INFINITY;
+        Bounds::new(Point::new(inf, inf, inf), Point::new(-inf, -inf, -inf))
+    }
+
+    pub fn infinite() -> Bounds {
+        let inf = CoordValue::INFINITY;
+        Bounds::new(Point::new(-inf, -inf, -inf), Point::new(inf, inf, inf))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x() > self.max.x() || self.min.y() > self.max.y() || self.min.z() > self.max.z()
+    }
+
+    /// Whether the box is neither empty nor infinite in any direction.
+    pub fn is_finite(&self) -> bool {
+        !self.is_empty()
+            && [self.min.as_coordinates(), self.max.as_coordinates()]
+                .iter()
+                .all(|&(x, y, z)| x.is_finite() && y.is_finite() && z.is_finite())
+    }
+
+    pub fn add_point(&mut self, point: &Point) {
+        let (x, y, z) = point.as_coordinates();
+        self.min = Point::new(
+            self.min.x().min(x),
+            self.min.y().min(y),
+            self.min.z().min(z),
+        );
+        self.max = Point::new(
+            self.max.x().max(x),
+            self.max.y().max(y),
+            self.max.z().max(z),
+        );
+    }
+
+    /// Grows the box to also contain `other`.
+    pub fn merge(&mut self, other: &Bounds) {
+        if !other.is_empty() {
+            self.add_point(&other.min);
+            self.add_point(&other.max);
+        }
+    }
+
+    pub fn contains_point(&self, point: &Point) -> bool {
+        let (x, y, z) = point.as_coordinates();
+        (self.min.x()..=self.max.x()).contains(&x)
+            && (self.min.y()..=self.max.y()).contains(&y)
+            && (self.min.z()..=self.max.z()).contains(&z)
+    }
+
+    pub fn contains_bounds(&self, other: &Bounds) -> bool {
+        other.is_empty() || (self.contains_point(&other.min) && self.contains_point(&other.max))
+    }
+
+    pub fn centroid(&self) -> Point {
+        Point::new(
+            (self.min.x() + self.max.x()) / 2.0,
+            (self.min.y() + self.max.y()) / 2.0,
+            (self.min.z() + self.max.z()) / 2.0,
+        )
+    }
+
+    /// Size of the box along x, y and z.
+    pub fn extent(&self) -> (CoordValue, CoordValue, CoordValue) {
+        if self.is_empty() {
+            return (0.0, 0.0, 0.0);
+        }
+        (
+            self.max.x() - self.min.x(),
+            self.max.y() - self.min.y(),
+            self.max.z() - self.min.z(),
+        )
+    }
+
+    pub fn surface_area(&self) -> CoordValue {
+        let (dx, dy, dz) = self.extent();
+        2.0 * (dx * dy + dy * dz + dz * dx)
+    }
+
+    /// Outward normal of the face of the box nearest to `point`, which is meant
+    /// to be on or inside the box. Boxes without any finite face, such as the
+    /// empty one, give +y.
+    pub fn normal_at(&self, point: &Point) -> Normal {
+        let (x, y, z) = point.as_coordinates();
+        let (min_x, min_y, min_z) = self.min.as_coordinates();
+        let (max_x, max_y, max_z) = self.max.as_coordinates();
+        let faces = [
+            ((x - min_x).abs(), (-1.0, 0.0, 0.0)),
+            ((x - max_x).abs(), (1.0, 0.0, 0.0)),
+            ((y - min_y).abs(), (0.0, -1.0, 0.0)),
+            ((y - max_y).abs(), (0.0, 1.0, 0.0)),
+            ((z - min_z).abs(), (0.0, 0.0, -1.0)),
+            ((z - max_z).abs(), (0.0, 0.0, 1.0)),
+        ];
+        let mut nearest = (CoordValue::INFINITY, (0.0, 1.0, 0.0));
+        for face in faces {
+            if face.0 < nearest.0 {
+                nearest = face;
+            }
+        }
+        let (nx, ny, nz) = nearest.1;
+        Normal::new(nx, ny, nz)
+    }
+
+    /// Bounds of this box once transformed by `m`, which contain the
+    /// transformed box but are usually larger. Infinite bounds stay infinite,
+    /// as transforming them could mix infinities of opposite signs.
+    pub fn transform(&self, m: &Matrix4) -> Bounds {
+        if self.is_empty() {
+            return Bounds::empty();
+        }
+        if !self.is_finite() {
+            return Bounds::infinite();
+        }
+        let mut bounds = Bounds::empty();
+        for x in [self.min.x(), self.max.x()] {
+            for y in [self.min.y(), self.max.y()] {
+                for z in [self.min.z(), self.max.z()] {
+                    bounds.add_point(&(m * &Point::new(x, y, z)));
+                }
+            }
+        }
+        bounds
+    }
+
+    /// Whether the line of `ray` crosses the box. Both directions count, as
+    /// shapes report intersections behind the ray origin too.
+    pub fn intersects(&self, ray: &Ray) -> bool {
+        if self.is_empty() {
+            return false;
+        }
+        let (xtmin, xtmax) = check_axis(
+            ray.origin.x(),
+            ray.direction.x(),
+            self.min.x(),
+            self.max.x(),
+        );
+        let (ytmin, ytmax) = check_axis(
+            ray.origin.y(),
+            ray.direction.y(),
+            self.min.y(),
+            self.max.y(),
+        );
+        let (ztmin, ztmax) = check_axis(
+            ray.origin.z(),
+            ray.direction.z(),
+            self.min.z(),
+            self.max.z(),
+        );
+        xtmin.max(ytmin).max(ztmin) <= xtmax.min(ytmax).min(ztmax)
+    }
+}
+
+impl Default for Bounds {
+    fn default() -> Self {
+        Bounds::empty()
+    }
+}
+
+/// Distances at which a ray crosses the two faces of the slab `[min, max]`
+/// along one axis, nearest first.
+///
+/// A ray parallel to the slab gets infinite distances, which keep it inside
+/// the slab if it starts there and outside otherwise, however small the scale.
+/// One lying exactly in the plane of a face is counted as inside.
+pub(crate) fn check_axis(
+    origin: CoordValue,
+    direction: CoordValue,
+    min: CoordValue,
+    max: CoordValue,
+) -> (CoordValue, CoordValue) {
+    let tmin = (min - origin) / direction;
+    let tmax = (max - origin) / direction;
+    if tmin.is_nan() || tmax.is_nan() {
+        (-CoordValue::INFINITY, CoordValue::INFINITY)
+    } else if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{rotation_x, rotation_y};
+    use crate::tuple::consts::{FRAC_1_SQRT_2, SQRT_2};
+    use crate::vector::Vector;
+    use std::f64::consts::FRAC_PI_4;
+
+    fn cube(min: CoordValue, max: CoordValue) -> Bounds {
+        Bounds::new(Point::new(min, min, min), Point::new(max, max, max))
+    }
+
+    #[test]
+    fn test_empty() {
+        let b = Bounds::empty();
+        assert!(b.is_empty());
+        assert!(!b.is_finite());
+        assert_eq!(b, Bounds::default());
+        assert_eq!(b.surface_area(), 0.0);
+    }
+
+    #[test]
+    fn test_add_points() {
+        let mut b = Bounds::empty();
+        b.add_point(&Point::new(-5.0, 2.0, 0.0));
+        b.add_point(&Point::new(7.0, 0.0, -3.0));
+        assert_eq!(b.min, Point::new(-5.0, 0.0, -3.0));
+        assert_eq!(b.max, Point::new(7.0, 2.0, 0.0));
+        assert!(b.is_finite());
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut b = Bounds::new(Point::new(-5.0, -2.0, 0.0), Point::new(7.0, 4.0, 4.0));
+        b.merge(&Bounds::new(
+            Point::new(8.0, -7.0, -2.0),
+            Point::new(14.0, 2.0, 8.0),
+        ));
+        b.merge(&Bounds::empty());
+        assert_eq!(b.min, Point::new(-5.0, -7.0, -2.0));
+        assert_eq!(b.max, Point::new(14.0, 4.0, 8.0));
+    }
+
+    #[test]
+    fn test_contains() {
+        let b = Bounds::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+        assert!(b.contains_point(&Point::new(5.0, -2.0, 0.0)));
+        assert!(b.contains_point(&Point::new(8.0, 1.0, 3.0)));
+        assert!(!b.contains_point(&Point::new(3.0, 0.0, 3.0)));
+        assert!(!b.contains_point(&Point::new(8.0, 1.0, 8.0)));
+        assert!(b.contains_bounds(&Bounds::new(
+            Point::new(6.0, -1.0, 1.0),
+            Point::new(10.0, 3.0, 6.0)
+        )));
+        assert!(!b.contains_bounds(&Bounds::new(
+            Point::new(4.0, -3.0, -1.0),
+            Point::new(10.0, 3.0, 6.0)
+        )));
+        assert!(b.contains_bounds(&Bounds::empty()));
+    }
+
+    #[test]
+    fn test_centroid_extent_and_area() {
+        let b = Bounds::new(Point::new(-1.0, 0.0, 2.0), Point::new(1.0, 3.0, 6.0));
+        assert_eq!(b.centroid(), Point::new(0.0, 1.5, 4.0));
+        assert_eq!(b.extent(), (2.0, 3.0, 4.0));
+        assert_eq!(b.surface_area(), 52.0);
+    }
+
+    #[test]
+    fn test_transform() {
+        let m =
+            Matrix4::try_from((&rotation_x(FRAC_PI_4) * &rotation_y(FRAC_PI_4)).unwrap()).unwrap();
+        let b = cube(-1.0, 1.0).transform(&m);
+        // The corners end up 1 + 1/√2 away from the center in y and z
+        let yz = 1.0 + FRAC_1_SQRT_2;
+        assert_approx_eq!(b.min, Point::new(-SQRT_2, -yz, -yz));
+        assert_approx_eq!(b.max, Point::new(SQRT_2, yz, yz));
+    }
+
+    #[test]
+    fn test_transform_infinite() {
+        let plane = Bounds::new(
+            Point::new(-CoordValue::INFINITY, 0.0, -CoordValue::INFINITY),
+            Point::new(CoordValue::INFINITY, 0.0, CoordValue::INFINITY),
+        );
+        let m = Matrix4::try_from(rotation_x(FRAC_PI_4)).unwrap();
+        assert_eq!(plane.transform(&m), Bounds::infinite());
+        assert_eq!(Bounds::empty().transform(&m), Bounds::empty());
+    }
+
+    #[test]
+    fn test_normal_at() {
+        let b = Bounds::new(Point::new(-1.0, 0.0, 2.0), Point::new(3.0, 1.0, 6.0));
+        let cases = [
+            (Point::new(-0.9, 0.5, 4.0), Normal::new(-1.0, 0.0, 0.0)),
+            (Point::new(2.9, 0.5, 4.0), Normal::new(1.0, 0.0, 0.0)),
+            (Point::new(1.0, 0.1, 4.0), Normal::new(0.0, -1.0, 0.0)),
+            (Point::new(1.0, 0.8, 4.0), Normal::new(0.0, 1.0, 0.0)),
+            (Point::new(1.0, 0.5, 2.0), Normal::new(0.0, 0.0, -1.0)),
+            (Point::new(1.0, 0.5, 5.8), Normal::new(0.0, 0.0, 1.0)),
+        ];
+        for (point, expected) in cases {
+            assert_eq!(b.normal_at(&point), expected, "{point:?}");
+        }
+        let origin = Point::new(0.0, 0.0, 0.0);
+        assert_eq!(
+            Bounds::empty().normal_at(&origin),
+            Normal::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            Bounds::infinite().normal_at(&origin),
+            Normal::new(0.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_intersects() {
+        let b = cube(-1.0, 1.0);
+        let cases = [
+            (Point::new(5.0, 0.5, 0.0), Vector::new(-1.0, 0.0, 0.0), true),
+            (Point::new(-5.0, 0.5, 0.0), Vector::new(1.0, 0.0, 0.0), true),
+            (Point::new(0.5, 5.0, 0.0), Vector::new(0.0, -1.0, 0.0), true),
+            (Point::new(0.0, 0.5, 0.0), Vector::new(0.0, 0.0, 1.0), true),
+            // Behind the ray origin
+            (Point::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, 1.0), true),
+            (
+                Point::new(-2.0, 0.0, 0.0),
+                Vector::new(2.0, 4.0, 6.0),
+                false,
+            ),
+            (
+                Point::new(0.0, -2.0, 0.0),
+                Vector::new(6.0, 2.0, 4.0),
+                false,
+            ),
+            (
+                Point::new(2.0, 2.0, 0.0),
+                Vector::new(0.0, 0.0, -1.0),
+                false,
+            ),
+        ];
+        for (origin, direction, expected) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(b.intersects(&r), expected, "{r:?}");
+        }
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(!Bounds::empty().intersects(&r));
+        assert!(Bounds::infinite().intersects(&r));
+    }
+
+    #[test]
+    fn test_intersects_with_tiny_direction_components() {
+        // What a ray becomes in the object space of a group scaled by 10000
+        let r = Ray::new(
+            Point::new(1.2, 0.0, -5.0),
+            Vector::new(-0.00003, 0.0, 0.0001),
+        );
+        assert!(cube(-1.0, 1.0).intersects(&r));
+        let r = Ray::new(
+            Point::new(1.2, 0.0, -5.0),
+            Vector::new(0.00003, 0.0, 0.0001),
+        );
+        assert!(!cube(-1.0, 1.0).intersects(&r));
+    }
+
+    #[test]
+    fn test_intersects_parallel_ray_in_face_plane() {
+        let r = Ray::new(Point::new(1.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(cube(-1.0, 1.0).intersects(&r));
+    }
+
+    #[test]
+    fn test_intersects_non_cubic_box() {
+        let b = Bounds::new(Point::new(5.0, -2.0, 0.0), Point::new(11.0, 4.0, 7.0));
+        let cases = [
+            (
+                Point::new(15.0, 1.0, 2.0),
+                Vector::new(-1.0, 0.0, 0.0),
+                true,
+            ),
+            (
+                Point::new(8.0, 2.0, 12.0),
+                Vector::new(0.0, 0.0, -1.0),
+                true,
+            ),
+            (
+                Point::new(9.0, -1.0, -8.0),
+                Vector::new(2.0, 4.0, 6.0),
+                false,
+            ),
+            (
+                Point::new(12.0, 5.0, 4.0),
+                Vector::new(-1.0, 0.0, 0.0),
+                false,
+            ),
+        ];
+        for (origin, direction, expected) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(b.intersects(&r), expected, "{r:?}");
+        }
+    }
+}
@@ -0,0 +1,470 @@
+use crate::bounds::Bounds;
+use crate::point::Point;
+use crate::ray::Ray;
+use crate::tuple::CoordValue;
+use std::fmt;
+use std::ops::Range;
+
+/// Settings of the surface area heuristic (SAH) used to build a [`Bvh`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct BvhConfig {
+    /// Largest number of items in a leaf. Smaller leaves are only split when
+    /// the heuristic says it pays off.
+    pub max_leaf_size: usize,
+    /// Number of candidate split positions per axis.
+    pub bins: usize,
+    /// Cost of testing a ray against the bounds of a node, relative to
+    /// intersecting an item.
+    pub traversal_cost: CoordValue,
+}
+
+impl BvhConfig {
+    pub fn new() -> BvhConfig {
+        BvhConfig {
+            max_leaf_size: 4,
+            bins: 12,
+            traversal_cost: 0.125,
+        }
+    }
+}
+
+impl Default for BvhConfig {
+    fn default() -> Self {
+        BvhConfig::new()
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Node {
+    Leaf {
+        bounds: Bounds,
+        start: usize,
+        count: usize,
+    },
+    Interior {
+        bounds: Bounds,
+        left: usize,
+        right: usize,
+    },
+}
+
+impl Node {
+    fn bounds(&self) -> &Bounds {
+        match self {
+            Node::Leaf { bounds, .. } | Node::Interior { bounds, .. } => bounds,
+        }
+    }
+}
+
+struct Item {
+    index: usize,
+    bounds: Bounds,
+    centroid: Point,
+}
+
+/// A bounding volume hierarchy over a list of items, such as the children of
+/// a group, so that a ray is only tested against the items whose boxes it
+/// crosses.
+///
+/// The tree refers to ranges of the items, which must therefore be stored in
+/// the order given by [`Bvh::build`]. Items with infinite (or empty) bounds
+/// cannot be partitioned: they come last and are always tested.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    bounded: usize,
+    len: usize,
+    traversal_cost: CoordValue,
+}
+
+impl Bvh {
+    /// Builds a hierarchy over items with the given bounds, returning it with
+    /// the order to store the items in: the index of the item to put first,
+    /// then second, and so on.
+    pub fn build(bounds: &[Bounds], config: &BvhConfig) -> (Bvh, Vec<usize>) {
+        let (mut bounded, unbounded): (Vec<Item>, Vec<Item>) = bounds
+            .iter()
+            .enumerate()
+            .map(|(index, bounds)| Item {
+                index,
+                centroid: bounds.centroid(),
+                bounds: bounds.clone(),
+            })
+            .partition(|item| item.bounds.is_finite());
+        let mut bvh = Bvh {
+            nodes: Vec::new(),
+            bounded: bounded.len(),
+            len: bounds.len(),
+            traversal_cost: config.traversal_cost,
+        };
+        if !bounded.is_empty() {
+            bvh.build_node(&mut bounded, 0, config);
+        }
+        let order = bounded
+            .iter()
+            .chain(unbounded.iter())
+            .map(|item| item.index)
+            .collect();
+        (bvh, order)
+    }
+
+    fn build_node(&mut self, items: &mut [Item], start: usize, config: &BvhConfig) -> usize {
+        let mut bounds = Bounds::empty();
+        for item in items.iter() {
+            bounds.merge(&item.bounds);
+        }
+        let node = self.nodes.len();
+        self.nodes.push(Node::Leaf {
+            bounds: bounds.clone(),
+            start,
+            count: items.len(),
+        });
+        if let Some(middle) = split(items, &bounds, config) {
+            let (left_items, right_items) = items.split_at_mut(middle);
+            let left = self.build_node(left_items, start, config);
+            let right = self.build_node(right_items, start + middle, config);
+            self.nodes[node] = Node::Interior {
+                bounds,
+                left,
+                right,
+            };
+        }
+        node
+    }
+
+    /// Number of items, bounded or not.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Bounds of the items in the tree, which exclude the unbounded ones.
+    pub fn bounds(&self) -> Bounds {
+        self.nodes
+            .first()
+            .map_or_else(Bounds::empty, |root| root.bounds().clone())
+    }
+
+    /// Calls `visit` with every range of items that `ray` may intersect.
+    pub fn traverse(&self, ray: &Ray, mut visit: impl FnMut(Range<usize>)) {
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push(0);
+        }
+        while let Some(node) = stack.pop() {
+            let node = &self.nodes[node];
+            if !node.bounds().intersects(ray) {
+                continue;
+            }
+            match node {
+                Node::Leaf { start, count, .. } => visit(*start..*start + *count),
+                Node::Interior { left, right, .. } => {
+                    stack.push(*right);
+                    stack.push(*left);
+                }
+            }
+        }
+        if self.bounded < self.len {
+            visit(self.bounded..self.len);
+        }
+    }
+
+    pub fn stats(&self) -> BvhStats {
+        let mut stats = BvhStats {
+            items: self.len,
+            unbounded: self.len - self.bounded,
+            nodes: self.nodes.len(),
+            leaves: 0,
+            depth: 0,
+            min_leaf_size: usize::MAX,
+            max_leaf_size: 0,
+            sah_cost: 0.0,
+        };
+        let root_area = self.bounds().surface_area();
+        let mut stack = Vec::new();
+        if !self.nodes.is_empty() {
+            stack.push((0, 1));
+        }
+        while let Some((node, depth)) = stack.pop() {
+            let node = &self.nodes[node];
+            // Odds that a ray hitting the root also hits this node
+            let probability = if root_area > 0.0 {
+                node.bounds().surface_area() / root_area
+            } else {
+                1.0
+            };
+            stats.depth = stats.depth.max(depth);
+            match node {
+                Node::Leaf { count, .. } => {
+                    stats.leaves += 1;
+                    stats.min_leaf_size = stats.min_leaf_size.min(*count);
+                    stats.max_leaf_size = stats.max_leaf_size.max(*count);
+                    stats.sah_cost += probability * *count as CoordValue;
+                }
+                Node::Interior { left, right, .. } => {
+                    stats.sah_cost += probability * self.traversal_cost;
+                    stack.push((*left, depth + 1));
+                    stack.push((*right, depth + 1));
+                }
+            }
+        }
+        if stats.leaves == 0 {
+            stats.min_leaf_size = 0;
+        }
+        stats.sah_cost += stats.unbounded as CoordValue;
+        stats
+    }
+}
+
+/// Partitions `items`, whose overall bounds are `bounds`, in two at the split
+/// that the surface area heuristic finds cheapest, returning where the second
+/// half starts. Returns `None` when the items are best left in a leaf.
+fn split(items: &mut [Item], bounds: &Bounds, config: &BvhConfig) -> Option<usize> {
+    let count = items.len();
+    if count <= 1 {
+        return None;
+    }
+    let mut centroid_bounds = Bounds::empty();
+    for item in items.iter() {
+        centroid_bounds.add_point(&item.centroid);
+    }
+    let extent = centroid_bounds.extent();
+    let extents = [extent.0, extent.1, extent.2];
+    let area = bounds.surface_area();
+    let bins = config.bins.max(2);
+
+    // (cost, axis, first bin of the second half)
+    let mut best: Option<(CoordValue, usize, usize)> = None;
+    for (axis, &axis_extent) in extents.iter().enumerate() {
+        if axis_extent <= 0.0 {
+            continue;
+        }
+        let mut bin_bounds = vec![Bounds::empty(); bins];
+        let mut bin_counts = vec![0; bins];
+        for item in items.iter() {
+            let bin = bin_of(&item.centroid, &centroid_bounds, axis, axis_extent, bins);
+            bin_bounds[bin].merge(&item.bounds);
+            bin_counts[bin] += 1;
+        }
+        for boundary in 1..bins {
+            let mut left = Bounds::empty();
+            let mut right = Bounds::empty();
+            for bin_bounds in &bin_bounds[..boundary] {
+                left.merge(bin_bounds);
+            }
+            for bin_bounds in &bin_bounds[boundary..] {
+                right.merge(bin_bounds);
+            }
+            let left_count: usize = bin_counts[..boundary].iter().sum();
+            let right_count = count - left_count;
+            if left_count == 0 || right_count == 0 {
+                continue;
+            }
+            let cost = if area > 0.0 {
+                config.traversal_cost
+                    + (left.surface_area() * left_count as CoordValue
+                        + right.surface_area() * right_count as CoordValue)
+                        / area
+            } else {
+                config.traversal_cost + count as CoordValue
+            };
+            if best.is_none_or(|(best_cost, _, _)| cost < best_cost) {
+                best = Some((cost, axis, boundary));
+            }
+        }
+    }
+
+    match best {
+        Some((cost, _, _)) if cost >= count as CoordValue && count <= config.max_leaf_size => None,
+        Some((_, axis, boundary)) => {
+            let extent = extents[axis];
+            Some(partition(items, |item| {
+                bin_of(&item.centroid, &centroid_bounds, axis, extent, bins) < boundary
+            }))
+        }
+        // All the centroids coincide, so any split is as good as another
+        None if count > config.max_leaf_size => Some(count / 2),
+        None => None,
+    }
+}
+
+fn bin_of(
+    centroid: &Point,
+    centroid_bounds: &Bounds,
+    axis: usize,
+    extent: CoordValue,
+    bins: usize,
+) -> usize {
+    let (value, min) = match axis {
+        0 => (centroid.x(), centroid_bounds.min.x()),
+        1 => (centroid.y(), centroid_bounds.min.y()),
+        _ => (centroid.z(), centroid_bounds.min.z()),
+    };
+    (((value - min) / extent * bins as CoordValue) as usize).min(bins - 1)
+}
+
+/// Moves the items for which `predicate` holds to the front, returning how
+/// many there are.
+fn partition(items: &mut [Item], predicate: impl Fn(&Item) -> bool) -> usize {
+    let mut first_false = 0;
+    for i in 0..items.len() {
+        if predicate(&items[i]) {
+            items.swap(first_false, i);
+            first_false += 1;
+        }
+    }
+    first_false
+}
+
+/// Shape of a [`Bvh`], to check how well it fits the items it was built for.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BvhStats {
+    pub items: usize,
+    /// Items outside the tree, tested by every ray.
+    pub unbounded: usize,
+    pub nodes: usize,
+    pub leaves: usize,
+    /// Number of nodes on the longest path from the root to a leaf.
+    pub depth: usize,
+    pub min_leaf_size: usize,
+    pub max_leaf_size: usize,
+    /// Expected cost of a ray crossing the root, in item intersections, as
+    /// estimated by the surface area heuristic.
+    pub sah_cost: CoordValue,
+}
+
+impl fmt::Display for BvhStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "BVH over {} items ({} unbounded)",
+            self.items, self.unbounded
+        )?;
+        writeln!(
+            f,
+            "  nodes: {} ({} leaves), depth {}",
+            self.nodes, self.leaves, self.depth
+        )?;
+        writeln!(
+            f,
+            "  leaf size: {} to {}",
+            self.min_leaf_size, self.max_leaf_size
+        )?;
+        write!(f, "  SAH cost: {:.2}", self.sah_cost)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::Vector;
+
+    fn unit_box_at(x: CoordValue, y: CoordValue, z: CoordValue) -> Bounds {
+        Bounds::new(
+            Point::new(x - 0.5, y - 0.5, z - 0.5),
+            Point::new(x + 0.5, y + 0.5, z + 0.5),
+        )
+    }
+
+    /// Indices, in the original order, of the items `ray` may intersect.
+    fn candidates(bvh: &Bvh, order: &[usize], ray: &Ray) -> Vec<usize> {
+        let mut result = Vec::new();
+        bvh.traverse(ray, |range| result.extend(range.map(|i| order[i])));
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn test_empty() {
+        let (bvh, order) = Bvh::build(&[], &BvhConfig::default());
+        assert!(bvh.is_empty());
+        assert!(order.is_empty());
+        assert!(bvh.bounds().is_empty());
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(candidates(&bvh, &order, &r).is_empty());
+    }
+
+    #[test]
+    fn test_order_is_a_permutation() {
+        let bounds: Vec<Bounds> = (0..50)
+            .map(|i| {
+                unit_box_at(
+                    (i % 7) as CoordValue * 3.0,
+                    (i / 7) as CoordValue * 3.0,
+                    0.0,
+                )
+            })
+            .collect();
+        let (bvh, mut order) = Bvh::build(&bounds, &BvhConfig::default());
+        assert_eq!(bvh.len(), 50);
+        order.sort();
+        assert_eq!(order, (0..50).collect::<Vec<usize>>());
+    }
+
+    #[test]
+    fn test_traverse_skips_distant_items() {
+        let bounds: Vec<Bounds> = (0..100)
+            .map(|i| unit_box_at(i as CoordValue * 2.0, 0.0, 0.0))
+            .collect();
+        let (bvh, order) = Bvh::build(&bounds, &BvhConfig::default());
+        // A ray along z through the item at x = 20 only
+        let r = Ray::new(Point::new(20.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let found = candidates(&bvh, &order, &r);
+        assert!(found.contains(&10));
+        assert!(found.len() <= BvhConfig::default().max_leaf_size);
+        // A ray along x crosses them all
+        let r = Ray::new(Point::new(-5.0, 0.0, 0.0), Vector::new(1.0, 0.0, 0.0));
+        assert_eq!(
+            candidates(&bvh, &order, &r),
+            (0..100).collect::<Vec<usize>>()
+        );
+    }
+
+    #[test]
+    fn test_unbounded_items_are_always_visited() {
+        let bounds = vec![
+            unit_box_at(0.0, 0.0, 0.0),
+            Bounds::infinite(),
+            unit_box_at(10.0, 0.0, 0.0),
+            Bounds::empty(),
+        ];
+        let (bvh, order) = Bvh::build(&bounds, &BvhConfig::default());
+        assert_eq!(&order[2..], &[1, 3]);
+        assert_eq!(bvh.bounds().max, Point::new(10.5, 0.5, 0.5));
+        let r = Ray::new(Point::new(0.0, 50.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(candidates(&bvh, &order, &r), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_coincident_items_respect_max_leaf_size() {
+        let bounds = vec![unit_box_at(1.0, 1.0, 1.0); 10];
+        let (bvh, _) = Bvh::build(&bounds, &BvhConfig::default());
+        assert!(bvh.stats().max_leaf_size <= BvhConfig::default().max_leaf_size);
+    }
+
+    #[test]
+    fn test_stats() {
+        let bounds: Vec<Bounds> = (0..64)
+            .map(|i| unit_box_at(i as CoordValue * 2.0, 0.0, 0.0))
+            .collect();
+        let config = BvhConfig {
+            max_leaf_size: 1,
+            ..BvhConfig::default()
+        };
+        let stats = Bvh::build(&bounds, &config).0.stats();
+        assert_eq!(stats.items, 64);
+        assert_eq!(stats.unbounded, 0);
+        assert_eq!(stats.leaves, 64);
+        assert_eq!(stats.nodes, 127);
+        assert_eq!((stats.min_leaf_size, stats.max_leaf_size), (1, 1));
+        assert!(stats.depth >= 7);
+        // Far cheaper than testing every item
+        assert!(stats.sah_cost < 64.0);
+        assert!(stats
+            .to_string()
+            .starts_with("BVH over 64 items (0 unbounded)\n  nodes: 127 (64 leaves)"));
+    }
+}
@@ -0,0 +1,181 @@
+use crate::canvas::Canvas;
+use crate::fixed_matrix::Matrix4;
+use crate::matrix::{Matrix, MatrixError};
+use crate::point::Point;
+use crate::ray::Ray;
+use crate::tuple::CoordValue;
+use crate::world::World;
+
+/// Maps the pixels of a `hsize` x `vsize` canvas onto a view of the world. The
+/// canvas sits one unit in front of the camera, which looks towards -z unless
+/// transformed, e.g. by a [`view_transform`](crate::transform::view_transform).
+#[derive(Debug, Clone)]
+pub struct Camera {
+    hsize: usize,
+    vsize: usize,
+    field_of_view: CoordValue,
+    transform: Matrix4,
+    inverse_transform: Matrix4,
+    half_width: CoordValue,
+    half_height: CoordValue,
+    pixel_size: CoordValue,
+}
+
+impl Camera {
+    pub fn new(hsize: usize, vsize: usize, field_of_view: CoordValue) -> Camera {
+        let half_view = (field_of_view / 2.0).tan();
+        let aspect = hsize as CoordValue / vsize as CoordValue;
+        let (half_width, half_height) = if aspect >= 1.0 {
+            (half_view, half_view / aspect)
+        } else {
+            (half_view * aspect, half_view)
+        };
+        Camera {
+            hsize,
+            vsize,
+            field_of_view,
+            transform: Matrix4::identity(),
+            inverse_transform: Matrix4::identity(),
+            half_width,
+            half_height,
+            pixel_size: half_width * 2.0 / hsize as CoordValue,
+        }
+    }
+
+    pub fn hsize(&self) -> usize {
+        self.hsize
+    }
+
+    pub fn vsize(&self) -> usize {
+        self.vsize
+    }
+
+    pub fn field_of_view(&self) -> CoordValue {
+        self.field_of_view
+    }
+
+    /// Size of a pixel on the canvas, in world units.
+    pub fn pixel_size(&self) -> CoordValue {
+        self.pixel_size
+    }
+
+    pub fn transform(&self) -> &Matrix4 {
+        &self.transform
+    }
+
+    /// Sets the world-to-camera transformation, which must be an invertible
+    /// 4x4 matrix.
+    pub fn set_transform(&mut self, transform: Matrix) -> Result<(), MatrixError> {
+        let transform = Matrix4::try_from(transform)?;
+        self.inverse_transform = transform.inverse()?;
+        self.transform = transform;
+        Ok(())
+    }
+
+    /// Ray from the camera through the center of the pixel at column `px`,
+    /// row `py`.
+    pub fn ray_for_pixel(&self, px: usize, py: usize) -> Ray {
+        let x_offset = (px as CoordValue + 0.5) * self.pixel_size;
+        let y_offset = (py as CoordValue + 0.5) * self.pixel_size;
+        // The camera looks towards -z, so +x is to the left
+        let world_x = self.half_width - x_offset;
+        let world_y = self.half_height - y_offset;
+
+        let pixel = &self.inverse_transform * &Point::new(world_x, world_y, -1.0);
+        let origin = &self.inverse_transform * &Point::new(0.0, 0.0, 0.0);
+        let direction = (&pixel - &origin).normalize();
+        Ray::new(origin, direction)
+    }
+
+    pub fn render(&self, world: &World) -> Canvas {
+        let mut canvas = Canvas::new(self.hsize, self.vsize);
+        for y in 0..self.vsize {
+            for x in 0..self.hsize {
+                let ray = self.ray_for_pixel(x, y);
+                canvas.write_pixel(x, y, world.color_at(&ray));
+            }
+        }
+        canvas
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+    use crate::transform::{rotation_y, translation, view_transform};
+    use crate::tuple::consts::{FRAC_1_SQRT_2, PI};
+    use crate::vector::Vector;
+    use crate::world::default_world;
+    use std::f64::consts::FRAC_PI_4;
+
+    #[test]
+    fn test_new() {
+        let c = Camera::new(160, 120, PI / 2.0);
+        assert_eq!(c.hsize(), 160);
+        assert_eq!(c.vsize(), 120);
+        assert_eq!(c.field_of_view(), PI / 2.0);
+        assert_eq!(*c.transform(), Matrix4::identity());
+    }
+
+    #[test]
+    fn test_pixel_size_for_horizontal_canvas() {
+        assert_approx_eq!(Camera::new(200, 125, PI / 2.0).pixel_size(), 0.01);
+    }
+
+    #[test]
+    fn test_pixel_size_for_vertical_canvas() {
+        assert_approx_eq!(Camera::new(125, 200, PI / 2.0).pixel_size(), 0.01);
+    }
+
+    #[test]
+    fn test_ray_through_center_of_canvas() {
+        let c = Camera::new(201, 101, PI / 2.0);
+        let r = c.ray_for_pixel(100, 50);
+        assert_approx_eq!(r.origin, Point::new(0.0, 0.0, 0.0));
+        assert_approx_eq!(r.direction, Vector::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_ray_through_corner_of_canvas() {
+        let c = Camera::new(201, 101, PI / 2.0);
+        let r = c.ray_for_pixel(0, 0);
+        assert_approx_eq!(r.origin, Point::new(0.0, 0.0, 0.0));
+        assert_approx_eq!(r.direction, Vector::new(0.66519, 0.33259, -0.66851), 0.0001);
+    }
+
+    #[test]
+    fn test_ray_when_camera_is_transformed() {
+        let mut c = Camera::new(201, 101, PI / 2.0);
+        c.set_transform((&rotation_y(FRAC_PI_4) * &translation(0.0, -2.0, 5.0)).unwrap())
+            .unwrap();
+        let r = c.ray_for_pixel(100, 50);
+        assert_approx_eq!(r.origin, Point::new(0.0, 2.0, -5.0));
+        assert_approx_eq!(r.direction, Vector::new(FRAC_1_SQRT_2, 0.0, -FRAC_1_SQRT_2));
+    }
+
+    #[test]
+    fn test_set_transform_rejects_singular_matrix() {
+        let mut c = Camera::new(10, 10, PI / 2.0);
+        assert_eq!(
+            c.set_transform(Matrix::new(4, 4)),
+            Err(MatrixError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn test_render() {
+        let w = default_world();
+        let mut c = Camera::new(11, 11, PI / 2.0);
+        c.set_transform(view_transform(
+            &Point::new(0.0, 0.0, -5.0),
+            &Point::new(0.0, 0.0, 0.0),
+            &Vector::new(0.0, 1.0, 0.0),
+        ))
+        .unwrap();
+        let image = c.render(&w);
+        assert_eq!((image.width, image.height), (11, 11));
+        assert_approx_eq!(image.pixel_at(5, 5), Color::new(0.38066, 0.47583, 0.2855));
+    }
+}
@@ -1,4 +1,5 @@
 use crate::color::Color;
+use crate::tuple::CoordValue;
 
 pub struct Canvas {
     pub width: usize,
@@ -31,7 +32,7 @@ impl Canvas {
         ppm.push_str(&format!("{PPM_HEADER}\r"));
         ppm.push_str(&format!("{} {}\r", self.width, self.height));
         ppm.push_str(&format!("{MAX_COLOR_VALUE}\r"));
-        fn cap_number(min: f32, max: f32, n: f32) -> f32 {
+        fn cap_number(min: CoordValue, max: CoordValue, n: CoordValue) -> CoordValue {
             if n < min {
                 min
             } else if n > max {
@@ -40,8 +41,9 @@ impl Canvas {
                 n
             }
         }
-        let scale_color =
-            |n: f32| -> u8 { (cap_number(0.0, 1.0, n) * MAX_COLOR_VALUE as f32).round() as u8 };
+        let scale_color = |n: CoordValue| -> u8 {
+            (cap_number(0.0, 1.0, n) * MAX_COLOR_VALUE as CoordValue).round() as u8
+        };
         for y in 0..self.height {
             let mut line = String::new();
             for x in 0..self.width {
@@ -67,6 +69,44 @@ impl Canvas {
     }
 }
 
+/// Only the dimensions of a canvas are serialized; a deserialized canvas is
+/// blank.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct CanvasMetadata {
+    width: usize,
+    height: usize,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Canvas {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        CanvasMetadata {
+            width: self.width,
+            height: self.height,
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Largest canvas, in pixels, that deserialization will allocate.
+#[cfg(feature = "serde")]
+pub const MAX_DESERIALIZED_PIXELS: usize = 1 << 24;
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Canvas {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Canvas, D::Error> {
+        let m = CanvasMetadata::deserialize(deserializer)?;
+        match m.width.checked_mul(m.height) {
+            Some(pixels) if pixels <= MAX_DESERIALIZED_PIXELS => Ok(Canvas::new(m.width, m.height)),
+            _ => Err(serde::de::Error::custom(format!(
+                "canvas of {}x{} pixels is too large",
+                m.width, m.height
+            ))),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,4 +189,38 @@ mod tests {
         let ppm = c.to_ppm();
         assert_eq!(ppm.chars().last().unwrap(), '\r');
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_keeps_only_dimensions() {
+        let mut c = Canvas::new(5, 3);
+        c.write_pixel(1, 1, Color::new(1.0, 0.0, 0.0));
+        let json = serde_json::to_string(&c).unwrap();
+        assert_eq!(json, r#"{"width":5,"height":3}"#);
+        let back: Canvas = serde_json::from_str(&json).unwrap();
+        assert_eq!((back.width, back.height), (5, 3));
+        assert_eq!(back.pixel_at(1, 1), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_huge_canvas() {
+        let json = format!(r#"{{"width":{},"height":2}}"#, usize::MAX);
+        let error = serde_json::from_str::<Canvas>(&json).err().unwrap();
+        assert!(error.to_string().contains("too large"), "{error}");
+        let error = serde_json::from_str::<Canvas>(r#"{"width":65536,"height":65536}"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("too large"), "{error}");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_canvas_just_over_limit() {
+        assert_eq!(4096 * 4096, MAX_DESERIALIZED_PIXELS);
+        let error = serde_json::from_str::<Canvas>(r#"{"width":4097,"height":4096}"#)
+            .err()
+            .unwrap();
+        assert!(error.to_string().contains("too large"), "{error}");
+    }
 }
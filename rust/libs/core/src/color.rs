@@ -1,6 +1,12 @@
-use crate::tuple::{CoordValue, Tuple};
+use crate::tuple::{forward_binop, forward_neg, forward_scalar_op, CoordValue, Tuple};
+use std::ops;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "Rgb", into = "Rgb")
+)]
 pub struct Color {
     tuple: Tuple,
 }
@@ -62,6 +68,12 @@ impl Color {
         }
     }
 
+    pub fn neg(&self) -> Color {
+        Color {
+            tuple: self.tuple.neg(),
+        }
+    }
+
     pub fn mul(&self, scalar: CoordValue) -> Color {
         Color {
             tuple: self.tuple.scalar_mul(scalar),
@@ -77,12 +89,96 @@ impl Color {
     }
 }
 
+/// Serialized form of colors.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct Rgb {
+    red: CoordValue,
+    green: CoordValue,
+    blue: CoordValue,
+}
+
+#[cfg(feature = "serde")]
+impl From<Rgb> for Color {
+    fn from(c: Rgb) -> Color {
+        Color::new(c.red, c.green, c.blue)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Color> for Rgb {
+    fn from(c: Color) -> Rgb {
+        Rgb {
+            red: c.red(),
+            green: c.green(),
+            blue: c.blue(),
+        }
+    }
+}
+
 impl PartialEq for Color {
     fn eq(&self, other: &Color) -> bool {
         self.tuple == other.tuple
     }
 }
 
+impl ops::Add<&Color> for &Color {
+    type Output = Color;
+
+    fn add(self, other: &Color) -> Color {
+        Color::add(self, other)
+    }
+}
+
+impl ops::Sub<&Color> for &Color {
+    type Output = Color;
+
+    fn sub(self, other: &Color) -> Color {
+        Color::sub(self, other)
+    }
+}
+
+impl ops::Neg for &Color {
+    type Output = Color;
+
+    fn neg(self) -> Color {
+        Color::neg(self)
+    }
+}
+
+impl ops::Mul<&Color> for &Color {
+    type Output = Color;
+
+    fn mul(self, other: &Color) -> Color {
+        self.hadamard_product(other)
+    }
+}
+
+impl ops::Mul<CoordValue> for &Color {
+    type Output = Color;
+
+    fn mul(self, scalar: CoordValue) -> Color {
+        Color::mul(self, scalar)
+    }
+}
+
+impl ops::Div<CoordValue> for &Color {
+    type Output = Color;
+
+    fn div(self, scalar: CoordValue) -> Color {
+        Color {
+            tuple: self.tuple.scalar_div(scalar),
+        }
+    }
+}
+
+forward_binop!(Color, Add, add, AddAssign, add_assign, Color, Color);
+forward_binop!(Color, Sub, sub, SubAssign, sub_assign, Color, Color);
+forward_binop!(Color, Mul, mul, MulAssign, mul_assign, Color, Color);
+forward_neg!(Color);
+forward_scalar_op!(Color, Mul, mul, MulAssign, mul_assign);
+forward_scalar_op!(Color, Div, div, DivAssign, div_assign);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,6 +245,15 @@ mod tests {
         assert_eq!(c3.blue(), -3.0);
     }
 
+    #[test]
+    fn test_neg() {
+        let c1 = Color::new(1.0, -2.0, 3.0);
+        let c2 = c1.neg();
+        assert_eq!(c2.red(), -1.0);
+        assert_eq!(c2.green(), 2.0);
+        assert_eq!(c2.blue(), -3.0);
+    }
+
     #[test]
     fn test_mul() {
         let c1 = Color::new(1.0, 2.0, 3.0);
@@ -168,4 +273,50 @@ mod tests {
         assert_eq!(c3.green(), 10.0);
         assert_eq!(c3.blue(), 18.0);
     }
+
+    #[test]
+    fn test_operators() {
+        let c1 = Color::new(1.0, 2.0, 3.0);
+        let c2 = Color::new(4.0, 5.0, 6.0);
+        assert_eq!(&c1 + &c2, Color::new(5.0, 7.0, 9.0));
+        assert_eq!(&c1 - &c2, Color::new(-3.0, -3.0, -3.0));
+        assert_eq!(&c1 * 2.0, Color::new(2.0, 4.0, 6.0));
+        assert_eq!(&c1 / 2.0, Color::new(0.5, 1.0, 1.5));
+        assert_eq!(c1.clone() + c2.clone(), Color::new(5.0, 7.0, 9.0));
+        assert_eq!(-&c2, Color::new(-4.0, -5.0, -6.0));
+        assert_eq!(-c2, Color::new(-4.0, -5.0, -6.0));
+        assert_eq!(c1 * 2.0, Color::new(2.0, 4.0, 6.0));
+    }
+
+    #[test]
+    fn test_mul_colors_is_hadamard_product() {
+        let c1 = Color::new(1.0, 0.2, 0.4);
+        let c2 = Color::new(0.9, 1.0, 0.1);
+        assert_eq!(&c1 * &c2, c1.hadamard_product(&c2));
+        assert_eq!(c1.clone() * c2.clone(), c1.hadamard_product(&c2));
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let mut c = Color::new(1.0, 2.0, 3.0);
+        c += Color::new(1.0, 1.0, 1.0);
+        assert_eq!(c, Color::new(2.0, 3.0, 4.0));
+        c -= &Color::new(2.0, 2.0, 2.0);
+        assert_eq!(c, Color::new(0.0, 1.0, 2.0));
+        c *= 4.0;
+        assert_eq!(c, Color::new(0.0, 4.0, 8.0));
+        c *= Color::new(1.0, 0.5, 0.25);
+        assert_eq!(c, Color::new(0.0, 2.0, 2.0));
+        c /= 2.0;
+        assert_eq!(c, Color::new(0.0, 1.0, 1.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let c = Color::new(0.5, 0.25, 1.0);
+        let json = serde_json::to_string(&c).unwrap();
+        assert_eq!(json, r#"{"red":0.5,"green":0.25,"blue":1.0}"#);
+        assert_eq!(serde_json::from_str::<Color>(&json).unwrap(), c);
+    }
 }
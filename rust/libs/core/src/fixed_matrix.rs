@@ -0,0 +1,537 @@
+use crate::matrix::{is_usable_determinant, Matrix, MatrixError};
+use crate::point::Point;
+use crate::tuple::{widen, CoordValue, Tuple};
+use crate::vector::Vector;
+use std::ops::Mul;
+
+/// Stack-allocated matrix whose dimensions are checked at compile time.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<Vec<f64>>", into = "Vec<Vec<f64>>")
+)]
+pub struct FixedMatrix<const R: usize, const C: usize> {
+    data: [[f64; C]; R],
+}
+
+pub type Matrix2 = FixedMatrix<2, 2>;
+pub type Matrix3 = FixedMatrix<3, 3>;
+pub type Matrix4 = FixedMatrix<4, 4>;
+
+impl<const R: usize, const C: usize> FixedMatrix<R, C> {
+    pub fn new() -> FixedMatrix<R, C> {
+        FixedMatrix {
+            data: [[0.0; C]; R],
+        }
+    }
+
+    pub fn from_array(data: [[f64; C]; R]) -> FixedMatrix<R, C> {
+        FixedMatrix { data }
+    }
+
+    pub fn rows(&self) -> usize {
+        R
+    }
+
+    pub fn columns(&self) -> usize {
+        C
+    }
+
+    pub fn get(&self, row: usize, column: usize) -> f64 {
+        self.data[row][column]
+    }
+
+    pub fn get_row(&self, row: usize) -> [f64; C] {
+        self.data[row]
+    }
+
+    pub fn get_column(&self, column: usize) -> [f64; R] {
+        let mut c = [0.0; R];
+        for (i, value) in c.iter_mut().enumerate() {
+            *value = self.data[i][column];
+        }
+        c
+    }
+
+    pub fn as_array(&self) -> &[[f64; C]; R] {
+        &self.data
+    }
+
+    pub fn transpose(&self) -> FixedMatrix<C, R> {
+        let mut data = [[0.0; R]; C];
+        for (i, row) in self.data.iter().enumerate() {
+            for (j, value) in row.iter().enumerate() {
+                data[j][i] = *value;
+            }
+        }
+        FixedMatrix { data }
+    }
+}
+
+impl<const N: usize> FixedMatrix<N, N> {
+    pub fn identity() -> FixedMatrix<N, N> {
+        let mut m = FixedMatrix::new();
+        for i in 0..N {
+            m.data[i][i] = 1.0;
+        }
+        m
+    }
+
+    pub fn get_diagonal(&self) -> [f64; N] {
+        let mut d = [0.0; N];
+        for (i, value) in d.iter_mut().enumerate() {
+            *value = self.data[i][i];
+        }
+        d
+    }
+}
+
+impl FixedMatrix<1, 1> {
+    pub fn determinant(&self) -> f64 {
+        self.data[0][0]
+    }
+}
+
+/// Determinant, cofactors and inverse of a square matrix whose submatrices are
+/// `$sub`, computed on the arrays without going through `Matrix`.
+macro_rules! impl_square {
+    ($matrix:ty, $sub:ty) => {
+        impl $matrix {
+            pub fn submatrix(&self, row: usize, column: usize) -> $sub {
+                let mut sub = <$sub>::new();
+                let rows = (0..self.rows()).filter(|&i| i != row);
+                for (target, source) in sub.data.iter_mut().zip(rows) {
+                    let values = (0..self.columns())
+                        .filter(|&j| j != column)
+                        .map(|j| self.data[source][j]);
+                    for (value, v) in target.iter_mut().zip(values) {
+                        *value = v;
+                    }
+                }
+                sub
+            }
+
+            pub fn minor(&self, row: usize, column: usize) -> f64 {
+                self.submatrix(row, column).determinant()
+            }
+
+            pub fn cofactor(&self, row: usize, column: usize) -> f64 {
+                let minor = self.minor(row, column);
+                if (row + column).is_multiple_of(2) {
+                    minor
+                } else {
+                    -minor
+                }
+            }
+
+            pub fn determinant(&self) -> f64 {
+                (0..self.columns())
+                    .map(|column| self.data[0][column] * self.cofactor(0, column))
+                    .sum()
+            }
+
+            /// A matrix whose determinant is zero, infinite or NaN has no inverse.
+            pub fn is_invertible(&self) -> bool {
+                is_usable_determinant(self.determinant())
+            }
+
+            pub fn inverse(&self) -> Result<$matrix, MatrixError> {
+                let det = self.determinant();
+                if !is_usable_determinant(det) {
+                    return Err(MatrixError::NotInvertible);
+                }
+                let mut m = <$matrix>::new();
+                for row in 0..self.rows() {
+                    for column in 0..self.columns() {
+                        // Writing to [column][row] transposes the cofactor matrix
+                        m.data[column][row] = self.cofactor(row, column) / det;
+                    }
+                }
+                Ok(m)
+            }
+        }
+    };
+}
+
+impl_square!(Matrix2, FixedMatrix<1, 1>);
+impl_square!(Matrix3, Matrix2);
+impl_square!(Matrix4, Matrix3);
+
+impl<const R: usize, const C: usize> Default for FixedMatrix<R, C> {
+    fn default() -> Self {
+        FixedMatrix::new()
+    }
+}
+
+impl<const R: usize, const C: usize> TryFrom<&Matrix> for FixedMatrix<R, C> {
+    type Error = MatrixError;
+
+    fn try_from(m: &Matrix) -> Result<FixedMatrix<R, C>, MatrixError> {
+        if m.rows() != R || m.columns() != C {
+            return Err(MatrixError::SizeMismatch {
+                expected: (R, C),
+                found: (m.rows(), m.columns()),
+            });
+        }
+        let mut data = [[0.0; C]; R];
+        for (i, row) in data.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = m.get(i, j);
+            }
+        }
+        Ok(FixedMatrix { data })
+    }
+}
+
+impl<const R: usize, const C: usize> TryFrom<Matrix> for FixedMatrix<R, C> {
+    type Error = MatrixError;
+
+    fn try_from(m: Matrix) -> Result<FixedMatrix<R, C>, MatrixError> {
+        FixedMatrix::try_from(&m)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const R: usize, const C: usize> TryFrom<Vec<Vec<f64>>> for FixedMatrix<R, C> {
+    type Error = MatrixError;
+
+    fn try_from(rows: Vec<Vec<f64>>) -> Result<FixedMatrix<R, C>, MatrixError> {
+        FixedMatrix::try_from(Matrix::try_from(rows)?)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<const R: usize, const C: usize> From<FixedMatrix<R, C>> for Vec<Vec<f64>> {
+    fn from(m: FixedMatrix<R, C>) -> Vec<Vec<f64>> {
+        m.data.iter().map(|row| row.to_vec()).collect()
+    }
+}
+
+impl<const R: usize, const C: usize, const K: usize> Mul<FixedMatrix<C, K>> for FixedMatrix<R, C> {
+    type Output = FixedMatrix<R, K>;
+
+    fn mul(self, other: FixedMatrix<C, K>) -> FixedMatrix<R, K> {
+        let mut data = [[0.0; K]; R];
+        for (i, row) in data.iter_mut().enumerate() {
+            for (j, value) in row.iter_mut().enumerate() {
+                *value = (0..C).map(|k| self.data[i][k] * other.data[k][j]).sum();
+            }
+        }
+        FixedMatrix { data }
+    }
+}
+
+impl<const N: usize> FixedMatrix<N, N> {
+    // Only called for N = 3 (acting on x, y and z) and N = 4 (acting on the full
+    // homogeneous tuple); components beyond N are kept as they are.
+    fn mul_tuple(&self, t: &Tuple) -> Tuple {
+        let column = t.as_homogeneous_array().map(widen);
+        let mut result = t.as_homogeneous_array();
+        for (i, value) in result.iter_mut().take(N).enumerate() {
+            *value = (0..N).map(|k| self.data[i][k] * column[k]).sum::<f64>() as CoordValue;
+        }
+        Tuple::from_homogeneous_array(result)
+    }
+}
+
+macro_rules! impl_tuple_mul {
+    ($matrix:ty) => {
+        impl Mul<&Tuple> for &$matrix {
+            type Output = Tuple;
+
+            fn mul(self, t: &Tuple) -> Tuple {
+                self.mul_tuple(t)
+            }
+        }
+
+        impl Mul<Tuple> for $matrix {
+            type Output = Tuple;
+
+            fn mul(self, t: Tuple) -> Tuple {
+                self.mul_tuple(&t)
+            }
+        }
+
+        impl Mul<&Point> for &$matrix {
+            type Output = Point;
+
+            fn mul(self, p: &Point) -> Point {
+                Point::project(self.mul_tuple(&p.as_tuple()))
+            }
+        }
+
+        impl Mul<Point> for $matrix {
+            type Output = Point;
+
+            fn mul(self, p: Point) -> Point {
+                &self * &p
+            }
+        }
+
+        impl Mul<&Vector> for &$matrix {
+            type Output = Vector;
+
+            fn mul(self, v: &Vector) -> Vector {
+                Vector::from(self.mul_tuple(&v.as_tuple()))
+            }
+        }
+
+        impl Mul<Vector> for $matrix {
+            type Output = Vector;
+
+            fn mul(self, v: Vector) -> Vector {
+                &self * &v
+            }
+        }
+    };
+}
+
+impl_tuple_mul!(Matrix3);
+impl_tuple_mul!(Matrix4);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    #[test]
+    fn test_new() {
+        let m = Matrix4::new();
+        assert_eq!(m.rows(), 4);
+        assert_eq!(m.columns(), 4);
+        assert_eq!(m.get(3, 3), 0.0);
+    }
+
+    #[test]
+    fn test_from_array() {
+        let m = Matrix2::from_array([[1.0, 2.0], [3.0, 4.0]]);
+        assert_eq!(m.get(0, 0), 1.0);
+        assert_eq!(m.get(0, 1), 2.0);
+        assert_eq!(m.get(1, 0), 3.0);
+        assert_eq!(m.get(1, 1), 4.0);
+    }
+
+    #[test]
+    fn test_copy() {
+        let m1 = Matrix2::from_array([[1.0, 2.0], [3.0, 4.0]]);
+        let m2 = m1;
+        assert_eq!(m1, m2);
+    }
+
+    #[test]
+    fn test_get_row_and_column() {
+        let m = Matrix3::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0], [7.0, 8.0, 9.0]]);
+        assert_eq!(m.get_row(1), [4.0, 5.0, 6.0]);
+        assert_eq!(m.get_column(1), [2.0, 5.0, 8.0]);
+        assert_eq!(m.get_diagonal(), [1.0, 5.0, 9.0]);
+    }
+
+    #[test]
+    fn test_identity() {
+        let m = Matrix3::identity();
+        assert_eq!(
+            m,
+            Matrix3::from_array([[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]])
+        );
+    }
+
+    #[test]
+    fn test_transpose() {
+        let m = FixedMatrix::<2, 3>::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let t: FixedMatrix<3, 2> = m.transpose();
+        assert_eq!(
+            t,
+            FixedMatrix::from_array([[1.0, 4.0], [2.0, 5.0], [3.0, 6.0]])
+        );
+    }
+
+    #[test]
+    fn test_multiply() {
+        let a = Matrix4::from_array([
+            [1.0, 2.0, 3.0, 4.0],
+            [5.0, 6.0, 7.0, 8.0],
+            [9.0, 8.0, 7.0, 6.0],
+            [5.0, 4.0, 3.0, 2.0],
+        ]);
+        let b = Matrix4::from_array([
+            [-2.0, 1.0, 2.0, 3.0],
+            [3.0, 2.0, 1.0, -1.0],
+            [4.0, 3.0, 6.0, 5.0],
+            [1.0, 2.0, 7.0, 8.0],
+        ]);
+        assert_eq!(
+            a * b,
+            Matrix4::from_array([
+                [20.0, 22.0, 50.0, 48.0],
+                [44.0, 54.0, 114.0, 108.0],
+                [40.0, 58.0, 110.0, 102.0],
+                [16.0, 26.0, 46.0, 42.0],
+            ])
+        );
+    }
+
+    #[test]
+    fn test_multiply_non_square() {
+        let a = FixedMatrix::<2, 3>::from_array([[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]]);
+        let b = FixedMatrix::<3, 2>::from_array([[7.0, 8.0], [9.0, 10.0], [11.0, 12.0]]);
+        assert_eq!(a * b, Matrix2::from_array([[58.0, 64.0], [139.0, 154.0]]));
+    }
+
+    #[test]
+    fn test_multiply_by_tuple_point_and_vector() {
+        let a = Matrix3::from_array([[1.0, 2.0, 3.0], [2.0, 4.0, 4.0], [8.0, 6.0, 4.0]]);
+        assert_eq!(a * Tuple::new(1.0, 2.0, 3.0), Tuple::new(14.0, 22.0, 32.0));
+        assert_eq!(a * Point::new(1.0, 2.0, 3.0), Point::new(14.0, 22.0, 32.0));
+        assert_eq!(
+            a * Vector::new(1.0, 2.0, 3.0),
+            Vector::new(14.0, 22.0, 32.0)
+        );
+    }
+
+    #[test]
+    fn test_multiply_matrix4_by_point_and_vector() {
+        let translation = Matrix4::from_array([
+            [1.0, 0.0, 0.0, 5.0],
+            [0.0, 1.0, 0.0, -3.0],
+            [0.0, 0.0, 1.0, 2.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert_eq!(
+            translation * Point::new(-3.0, 4.0, 5.0),
+            Point::new(2.0, 1.0, 7.0)
+        );
+        assert_eq!(
+            translation * Vector::new(-3.0, 4.0, 5.0),
+            Vector::new(-3.0, 4.0, 5.0)
+        );
+        assert_eq!(
+            translation * Tuple::homogeneous(-3.0, 4.0, 5.0, 1.0),
+            Tuple::homogeneous(2.0, 1.0, 7.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_submatrix_minor_and_cofactor() {
+        let a = Matrix3::from_array([[3.0, 5.0, 0.0], [2.0, -1.0, -7.0], [6.0, -1.0, 5.0]]);
+        assert_eq!(
+            a.submatrix(1, 0),
+            Matrix2::from_array([[5.0, 0.0], [-1.0, 5.0]])
+        );
+        assert_eq!(a.minor(1, 0), 25.0);
+        assert_eq!(a.cofactor(0, 0), -12.0);
+        assert_eq!(a.cofactor(1, 0), -25.0);
+    }
+
+    #[test]
+    fn test_determinants() {
+        assert_eq!(FixedMatrix::<1, 1>::from_array([[7.0]]).determinant(), 7.0);
+        assert_eq!(
+            Matrix2::from_array([[1.0, 5.0], [-3.0, 2.0]]).determinant(),
+            17.0
+        );
+        let a = Matrix3::from_array([[1.0, 2.0, 6.0], [-5.0, 8.0, -4.0], [2.0, 6.0, 4.0]]);
+        assert_eq!(a.determinant(), -196.0);
+        let b = Matrix4::from_array([
+            [-2.0, -8.0, 3.0, 5.0],
+            [-3.0, 1.0, 7.0, 3.0],
+            [1.0, 2.0, -9.0, 6.0],
+            [-6.0, 7.0, 7.0, -9.0],
+        ]);
+        assert_eq!(b.determinant(), -4071.0);
+    }
+
+    #[test]
+    fn test_inverse_matches_dynamic_matrix() {
+        let a = Matrix2::from_array([[4.0, 7.0], [2.0, 6.0]]);
+        assert_approx_eq!(
+            Matrix::from(a.inverse().unwrap()),
+            Matrix::from(a).inverse().unwrap()
+        );
+        let b = Matrix3::from_array([[3.0, 0.0, 2.0], [2.0, 0.0, -2.0], [0.0, 1.0, 1.0]]);
+        assert_approx_eq!(
+            Matrix::from(b.inverse().unwrap()),
+            Matrix::from(b).inverse().unwrap()
+        );
+        let c = Matrix4::from_array([
+            [-5.0, 2.0, 6.0, -8.0],
+            [1.0, -5.0, 1.0, 8.0],
+            [7.0, 7.0, -6.0, -7.0],
+            [1.0, -3.0, 7.0, 4.0],
+        ]);
+        assert_approx_eq!(
+            Matrix::from(c.inverse().unwrap()),
+            Matrix::from(c).inverse().unwrap(),
+            0.00001
+        );
+    }
+
+    #[test]
+    fn test_determinant_and_inverse() {
+        let a = Matrix4::from_array([
+            [8.0, -5.0, 9.0, 2.0],
+            [7.0, 5.0, 6.0, 1.0],
+            [-6.0, 0.0, 9.0, 6.0],
+            [-3.0, 0.0, -9.0, -4.0],
+        ]);
+        assert!(a.is_invertible());
+        assert_eq!(a.determinant(), -585.0);
+        assert_approx_eq!(a * a.inverse().unwrap(), Matrix4::identity());
+    }
+
+    #[test]
+    fn test_inverse_of_singular_matrix() {
+        let a = Matrix2::from_array([[1.0, 2.0], [2.0, 4.0]]);
+        assert!(!a.is_invertible());
+        assert_eq!(a.inverse(), Err(MatrixError::NotInvertible));
+    }
+
+    #[test]
+    fn test_inverse_of_nan_matrix() {
+        let a = Matrix2::from_array([[f64::NAN, 0.0], [0.0, 1.0]]);
+        assert!(!a.is_invertible());
+        assert_eq!(a.inverse(), Err(MatrixError::NotInvertible));
+    }
+
+    #[test]
+    fn test_convert_to_dynamic_matrix() {
+        let a = Matrix2::from_array([[1.0, 2.0], [3.0, 4.0]]);
+        let m = Matrix::from(a);
+        assert_eq!(m, Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]));
+    }
+
+    #[test]
+    fn test_convert_from_dynamic_matrix() {
+        let m = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
+        let a = Matrix2::try_from(&m).unwrap();
+        assert_eq!(a, Matrix2::from_array([[1.0, 2.0], [3.0, 4.0]]));
+    }
+
+    #[test]
+    fn test_convert_from_dynamic_matrix_with_wrong_size() {
+        let m = Matrix::identity(3);
+        assert_eq!(
+            Matrix4::try_from(m),
+            Err(MatrixError::SizeMismatch {
+                expected: (4, 4),
+                found: (3, 3),
+            })
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let m = Matrix2::from_array([[1.0, 2.0], [3.0, 4.5]]);
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(json, "[[1.0,2.0],[3.0,4.5]]");
+        assert_eq!(serde_json::from_str::<Matrix2>(&json).unwrap(), m);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_wrong_size() {
+        let error = serde_json::from_str::<Matrix4>("[[1.0,2.0],[3.0,4.0]]").unwrap_err();
+        assert!(error.to_string().contains("4x4"), "{error}");
+    }
+}
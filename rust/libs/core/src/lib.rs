@@ -1,9 +1,36 @@
+//! A ray tracer.
+//!
+//! # Features
+//!
+//! - `f64`: stores coordinates and color channels as `f64` instead of `f32`.
+//!   This changes [`tuple::CoordValue`] and with it [`tuple::Tuple`],
+//!   [`point::Point`], [`vector::Vector`], [`normal::Normal`] and
+//!   [`color::Color`], and tightens [`approx::EPSILON`]. Matrices always
+//!   compute in `f64`.
+//! - `serde`: `Serialize` and `Deserialize` implementations for tuples,
+//!   matrices and canvas metadata.
+
+pub mod approx;
+pub mod bounds;
+pub mod bvh;
+pub mod camera;
 pub mod canvas;
 pub mod color;
+pub mod fixed_matrix;
+pub mod light;
+pub mod lighting;
+pub mod material;
 pub mod matrix;
+pub mod normal;
+pub mod obj;
+pub mod patterns;
 pub mod point;
+pub mod ray;
+pub mod shapes;
+pub mod transform;
 pub mod tuple;
 pub mod vector;
+pub mod world;
 
 #[cfg(test)]
 mod tests {}
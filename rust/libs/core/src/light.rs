@@ -0,0 +1,30 @@
+use crate::color::Color;
+use crate::point::Point;
+
+/// A light source with no size, emitting `intensity` in every direction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointLight {
+    pub position: Point,
+    pub intensity: Color,
+}
+
+impl PointLight {
+    pub fn new(position: Point, intensity: Color) -> PointLight {
+        PointLight {
+            position,
+            intensity,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new() {
+        let light = PointLight::new(Point::new(0.0, 0.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        assert_eq!(light.position, Point::new(0.0, 0.0, 0.0));
+        assert_eq!(light.intensity, Color::new(1.0, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,191 @@
+use crate::color::Color;
+use crate::light::PointLight;
+use crate::material::Material;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::shapes::Shape;
+use crate::vector::Vector;
+
+/// Shades `point` on `object` with the Phong reflection model, as seen along
+/// `eyev` (from the point towards the eye) on a surface with normal `normalv`.
+/// Points in shadow only get the ambient contribution.
+pub fn lighting(
+    material: &Material,
+    object: &dyn Shape,
+    light: &PointLight,
+    point: &Point,
+    eyev: &Vector,
+    normalv: &Normal,
+    in_shadow: bool,
+) -> Color {
+    let black = Color::new(0.0, 0.0, 0.0);
+    // Combine the surface color with the light's color/intensity
+    let effective_color = material
+        .color_at(object, point)
+        .hadamard_product(&light.intensity);
+    let ambient = &effective_color * material.ambient;
+    if in_shadow {
+        return ambient;
+    }
+
+    let lightv = (&light.position - point).normalize();
+    // Cosine of the angle between the light and the normal; a negative value
+    // means the light is on the other side of the surface
+    let light_dot_normal = normalv.dot(&lightv);
+    if light_dot_normal < 0.0 {
+        return ambient;
+    }
+    let diffuse = &effective_color * (material.diffuse * light_dot_normal);
+
+    // Cosine of the angle between the reflection and the eye; a negative value
+    // means the light reflects away from the eye
+    let reflectv = (-lightv).reflect(&normalv.as_vector());
+    let reflect_dot_eye = reflectv.dot(eyev);
+    let specular = if reflect_dot_eye <= 0.0 {
+        black
+    } else {
+        &light.intensity * (material.specular * reflect_dot_eye.powf(material.shininess))
+    };
+
+    &(&ambient + &diffuse) + &specular
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::patterns::Stripe;
+    use crate::shapes::Sphere;
+    use crate::tuple::consts::FRAC_1_SQRT_2;
+
+    fn shade(eyev: Vector, light_position: Point, in_shadow: bool) -> Color {
+        let light = PointLight::new(light_position, Color::new(1.0, 1.0, 1.0));
+        lighting(
+            &Material::default(),
+            &Sphere::new(),
+            &light,
+            &Point::new(0.0, 0.0, 0.0),
+            &eyev,
+            &Normal::new(0.0, 0.0, -1.0),
+            in_shadow,
+        )
+    }
+
+    #[test]
+    fn test_eye_between_light_and_surface() {
+        let result = shade(
+            Vector::new(0.0, 0.0, -1.0),
+            Point::new(0.0, 0.0, -10.0),
+            false,
+        );
+        assert_approx_eq!(result, Color::new(1.9, 1.9, 1.9));
+    }
+
+    #[test]
+    fn test_eye_offset_45_degrees() {
+        let result = shade(
+            Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            Point::new(0.0, 0.0, -10.0),
+            false,
+        );
+        assert_approx_eq!(result, Color::new(1.0, 1.0, 1.0));
+    }
+
+    #[test]
+    fn test_light_offset_45_degrees() {
+        let result = shade(
+            Vector::new(0.0, 0.0, -1.0),
+            Point::new(0.0, 10.0, -10.0),
+            false,
+        );
+        assert_approx_eq!(result, Color::new(0.7364, 0.7364, 0.7364));
+    }
+
+    #[test]
+    fn test_eye_in_path_of_reflection() {
+        let result = shade(
+            Vector::new(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            Point::new(0.0, 10.0, -10.0),
+            false,
+        );
+        assert_approx_eq!(result, Color::new(1.6364, 1.6364, 1.6364), 0.0001);
+    }
+
+    #[test]
+    fn test_light_behind_surface() {
+        let result = shade(
+            Vector::new(0.0, 0.0, -1.0),
+            Point::new(0.0, 0.0, 10.0),
+            false,
+        );
+        assert_approx_eq!(result, Color::new(0.1, 0.1, 0.1));
+    }
+
+    #[test]
+    fn test_surface_in_shadow() {
+        let result = shade(
+            Vector::new(0.0, 0.0, -1.0),
+            Point::new(0.0, 0.0, -10.0),
+            true,
+        );
+        assert_approx_eq!(result, Color::new(0.1, 0.1, 0.1));
+    }
+
+    #[test]
+    fn test_light_color_tints_result() {
+        let material = Material {
+            color: Color::new(1.0, 0.5, 0.0),
+            ..Material::default()
+        };
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 0.5));
+        let result = lighting(
+            &material,
+            &Sphere::new(),
+            &light,
+            &Point::new(0.0, 0.0, 0.0),
+            &Vector::new(0.0, 0.0, -1.0),
+            &Normal::new(0.0, 0.0, -1.0),
+            false,
+        );
+        // ambient + diffuse on the tinted color, specular on the light only
+        assert_approx_eq!(result, Color::new(1.9, 1.4, 0.45));
+    }
+
+    #[test]
+    fn test_pattern_applied() {
+        let mut material = Material {
+            ambient: 1.0,
+            diffuse: 0.0,
+            specular: 0.0,
+            ..Material::default()
+        };
+        material.set_pattern(Stripe::new(
+            Color::new(1.0, 1.0, 1.0),
+            Color::new(0.0, 0.0, 0.0),
+        ));
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        let eyev = Vector::new(0.0, 0.0, -1.0);
+        let normalv = Normal::new(0.0, 0.0, -1.0);
+        let object = Sphere::new();
+        let c1 = lighting(
+            &material,
+            &object,
+            &light,
+            &Point::new(0.9, 0.0, 0.0),
+            &eyev,
+            &normalv,
+            false,
+        );
+        let c2 = lighting(
+            &material,
+            &object,
+            &light,
+            &Point::new(1.1, 0.0, 0.0),
+            &eyev,
+            &normalv,
+            false,
+        );
+        assert_eq!(c1, Color::new(1.0, 1.0, 1.0));
+        assert_eq!(c2, Color::new(0.0, 0.0, 0.0));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::color::Color;
+use crate::patterns::Pattern;
+use crate::point::Point;
+use crate::shapes::Shape;
+use crate::tuple::CoordValue;
+use std::sync::Arc;
+
+/// Surface attributes of the Phong reflection model, plus how much the surface
+/// reflects and lets light through. When a pattern is set it replaces the
+/// flat `color`.
+#[derive(Debug, Clone)]
+pub struct Material {
+    pub color: Color,
+    pub pattern: Option<Arc<dyn Pattern>>,
+    pub ambient: CoordValue,
+    pub diffuse: CoordValue,
+    pub specular: CoordValue,
+    pub shininess: CoordValue,
+    /// 0 for a matte surface, 1 for a perfect mirror.
+    pub reflective: CoordValue,
+    /// 0 for an opaque surface, 1 for a perfectly clear one.
+    pub transparency: CoordValue,
+    /// How much light bends when entering the material: 1 for vacuum, about
+    /// 1.5 for glass.
+    pub refractive_index: CoordValue,
+}
+
+impl Material {
+    pub fn new() -> Material {
+        Material {
+            color: Color::new(1.0, 1.0, 1.0),
+            pattern: None,
+            ambient: 0.1,
+            diffuse: 0.9,
+            specular: 0.9,
+            shininess: 200.0,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+        }
+    }
+
+    pub fn set_pattern<P: Pattern + 'static>(&mut self, pattern: P) {
+        self.pattern = Some(Arc::new(pattern));
+    }
+
+    /// Surface color at a world-space point on `object`.
+    pub fn color_at(&self, object: &dyn Shape, point: &Point) -> Color {
+        match &self.pattern {
+            Some(pattern) => pattern.color_at_object(object, point),
+            None => self.color.clone(),
+        }
+    }
+}
+
+/// Patterns are compared by identity, as they can't be compared by value.
+impl PartialEq for Material {
+    fn eq(&self, other: &Self) -> bool {
+        let same_pattern = match (&self.pattern, &other.pattern) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            (None, None) => true,
+            _ => false,
+        };
+        same_pattern
+            && self.color == other.color
+            && self.ambient == other.ambient
+            && self.diffuse == other.diffuse
+            && self.specular == other.specular
+            && self.shininess == other.shininess
+            && self.reflective == other.reflective
+            && self.transparency == other.transparency
+            && self.refractive_index == other.refractive_index
+    }
+}
+
+impl Default for Material {
+    fn default() -> Self {
+        Material::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{Stripe, TestPattern};
+    use crate::shapes::Sphere;
+
+    #[test]
+    fn test_default() {
+        let m = Material::default();
+        assert_eq!(m.color, Color::new(1.0, 1.0, 1.0));
+        assert!(m.pattern.is_none());
+        assert_eq!(m.ambient, 0.1);
+        assert_eq!(m.diffuse, 0.9);
+        assert_eq!(m.specular, 0.9);
+        assert_eq!(m.shininess, 200.0);
+        assert_eq!(m.reflective, 0.0);
+        assert_eq!(m.transparency, 0.0);
+        assert_eq!(m.refractive_index, 1.0);
+    }
+
+    #[test]
+    fn test_color_at_without_pattern() {
+        let m = Material {
+            color: Color::new(0.2, 0.4, 0.6),
+            ..Material::default()
+        };
+        let c = m.color_at(&Sphere::new(), &Point::new(1.5, 0.0, 0.0));
+        assert_eq!(c, Color::new(0.2, 0.4, 0.6));
+    }
+
+    #[test]
+    fn test_color_at_with_pattern() {
+        let mut m = Material::default();
+        m.set_pattern(Stripe::new(
+            Color::new(1.0, 1.0, 1.0),
+            Color::new(0.0, 0.0, 0.0),
+        ));
+        let s = Sphere::new();
+        assert_eq!(
+            m.color_at(&s, &Point::new(0.5, 0.0, 0.0)),
+            Color::new(1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            m.color_at(&s, &Point::new(1.5, 0.0, 0.0)),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_eq_compares_patterns_by_identity() {
+        let mut a = Material::default();
+        a.set_pattern(TestPattern::new());
+        let b = a.clone();
+        assert_eq!(a, b);
+        let mut c = Material::default();
+        c.set_pattern(TestPattern::new());
+        assert_ne!(a, c);
+        assert_ne!(a, Material::default());
+    }
+}
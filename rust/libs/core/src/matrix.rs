@@ -1,4 +1,85 @@
-#[derive(Debug)]
+use crate::fixed_matrix::FixedMatrix;
+use crate::point::Point;
+use crate::tuple::{widen, CoordValue, Tuple};
+use crate::vector::Vector;
+use std::fmt;
+use std::ops::Mul;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MatrixError {
+    DimensionMismatch {
+        left: (usize, usize),
+        right: (usize, usize),
+    },
+    SizeMismatch {
+        expected: (usize, usize),
+        found: (usize, usize),
+    },
+    NotSquare {
+        rows: usize,
+        columns: usize,
+    },
+    NotInvertible,
+    Empty,
+    RaggedRow {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidNumber {
+        line: usize,
+        column: usize,
+        token: String,
+    },
+}
+
+impl fmt::Display for MatrixError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MatrixError::DimensionMismatch { left, right } => write!(
+                f,
+                "cannot multiply a {}x{} matrix by a {}x{} matrix",
+                left.0, left.1, right.0, right.1
+            ),
+            MatrixError::SizeMismatch { expected, found } => write!(
+                f,
+                "expected a {}x{} matrix, found {}x{}",
+                expected.0, expected.1, found.0, found.1
+            ),
+            MatrixError::NotSquare { rows, columns } => {
+                write!(f, "expected a square matrix, found {rows}x{columns}")
+            }
+            MatrixError::NotInvertible => write!(f, "matrix is not invertible"),
+            MatrixError::Empty => write!(f, "matrix has no values"),
+            MatrixError::RaggedRow {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "row at line {line} has {found} values, expected {expected}"
+            ),
+            MatrixError::InvalidNumber {
+                line,
+                column,
+                token,
+            } => write!(
+                f,
+                "invalid number '{token}' at line {line}, column {column}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MatrixError {}
+
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(try_from = "Vec<Vec<f64>>", into = "Vec<Vec<f64>>")
+)]
 pub struct Matrix {
     rows: usize,
     columns: usize,
@@ -14,36 +95,29 @@ impl Matrix {
         }
     }
 
-    pub fn from_string(s: &str) -> Matrix {
-        let mut rows = Vec::new();
-        for line in s.lines() {
-            let mut row = Vec::new();
-            for value in line.split_whitespace() {
-                row.push(value.parse::<f64>().unwrap());
-            }
-            rows.push(row);
-        }
-        Matrix {
-            rows: rows.len(),
-            columns: rows[0].len(),
-            data: rows,
+    pub fn identity(size: usize) -> Matrix {
+        let mut m = Matrix::new(size, size);
+        for i in 0..size {
+            m.data[i][i] = 1.0;
         }
+        m
     }
 
+    /// # Panics
+    ///
+    /// Panics if the string is not a valid matrix. Use `str::parse` to handle
+    /// the error instead.
+    pub fn from_string(s: &str) -> Matrix {
+        s.parse()
+            .unwrap_or_else(|e| panic!("invalid matrix string: {e}"))
+    }
+
+    /// # Panics
+    ///
+    /// Panics if the rows are empty or have different lengths. Use
+    /// `Matrix::try_from` to handle the error instead.
     pub fn from_vec(input_array: Vec<Vec<f64>>) -> Matrix {
-        let mut rows = Vec::new();
-        for row in input_array.iter() {
-            let mut r = Vec::new();
-            for value in row.iter() {
-                r.push(*value);
-            }
-            rows.push(r);
-        }
-        Matrix {
-            rows: rows.len(),
-            columns: rows[0].len(),
-            data: rows,
-        }
+        Matrix::try_from(input_array).unwrap_or_else(|e| panic!("invalid matrix rows: {e}"))
     }
 
     pub fn from_matrix(m: &Matrix) -> Matrix {
@@ -55,6 +129,14 @@ impl Matrix {
         }
     }
 
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    pub fn columns(&self) -> usize {
+        self.columns
+    }
+
     pub fn get(&self, row: usize, column: usize) -> f64 {
         self.data[row][column]
     }
@@ -94,6 +176,237 @@ impl Matrix {
         }
         d
     }
+
+    pub fn transpose(&self) -> Matrix {
+        Matrix {
+            rows: self.columns,
+            columns: self.rows,
+            data: self.get_columns(),
+        }
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.rows == self.columns
+    }
+
+    pub fn submatrix(&self, row: usize, column: usize) -> Matrix {
+        let data: Vec<Vec<f64>> = self
+            .data
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| *i != row)
+            .map(|(_, r)| {
+                r.iter()
+                    .enumerate()
+                    .filter(|(j, _)| *j != column)
+                    .map(|(_, value)| *value)
+                    .collect()
+            })
+            .collect();
+        Matrix {
+            rows: self.rows - 1,
+            columns: self.columns - 1,
+            data,
+        }
+    }
+
+    pub fn determinant(&self) -> Result<f64, MatrixError> {
+        if !self.is_square() {
+            return Err(MatrixError::NotSquare {
+                rows: self.rows,
+                columns: self.columns,
+            });
+        }
+        match self.rows {
+            0 => Ok(1.0),
+            1 => Ok(self.data[0][0]),
+            2 => Ok(self.data[0][0] * self.data[1][1] - self.data[0][1] * self.data[1][0]),
+            _ => {
+                let mut det = 0.0;
+                for column in 0..self.columns {
+                    det += self.data[0][column] * self.cofactor(0, column)?;
+                }
+                Ok(det)
+            }
+        }
+    }
+
+    pub fn minor(&self, row: usize, column: usize) -> Result<f64, MatrixError> {
+        self.submatrix(row, column).determinant()
+    }
+
+    pub fn cofactor(&self, row: usize, column: usize) -> Result<f64, MatrixError> {
+        let minor = self.minor(row, column)?;
+        if (row + column).is_multiple_of(2) {
+            Ok(minor)
+        } else {
+            Ok(-minor)
+        }
+    }
+
+    /// A matrix whose determinant is zero, infinite or NaN has no inverse.
+    pub fn is_invertible(&self) -> bool {
+        matches!(self.determinant(), Ok(det) if is_usable_determinant(det))
+    }
+
+    pub fn inverse(&self) -> Result<Matrix, MatrixError> {
+        let det = self.determinant()?;
+        if !is_usable_determinant(det) {
+            return Err(MatrixError::NotInvertible);
+        }
+        let mut m = Matrix::new(self.rows, self.columns);
+        for row in 0..self.rows {
+            for column in 0..self.columns {
+                // Writing to [column][row] transposes the cofactor matrix
+                m.data[column][row] = self.cofactor(row, column)? / det;
+            }
+        }
+        Ok(m)
+    }
+
+    /// 4x4 matrices act on the full homogeneous tuple, so translations move points
+    /// (w = 1) but leave vectors (w = 0) untouched. 3x3 matrices act on x, y and z
+    /// only and keep w as is.
+    fn mul_tuple(&self, t: &Tuple) -> Result<Tuple, MatrixError> {
+        let size = match (self.rows, self.columns) {
+            (4, 4) => 4,
+            (3, 3) => 3,
+            _ => {
+                return Err(MatrixError::DimensionMismatch {
+                    left: (self.rows, self.columns),
+                    right: (4, 1),
+                })
+            }
+        };
+        let column = t.as_homogeneous_array().map(widen);
+        let mut result = t.as_homogeneous_array();
+        for (i, value) in result.iter_mut().take(size).enumerate() {
+            *value = (0..size).map(|k| self.data[i][k] * column[k]).sum::<f64>() as CoordValue;
+        }
+        Ok(Tuple::from_homogeneous_array(result))
+    }
+}
+
+/// Whether a determinant is finite and nonzero, so its matrix can be inverted.
+pub(crate) fn is_usable_determinant(det: f64) -> bool {
+    det.is_finite() && det != 0.0
+}
+
+/// Column (1-based, in characters) where `token` starts within `line`. Both must
+/// come from the same string, as `token` is located by its address.
+fn column_of(line: &str, token: &str) -> usize {
+    let offset = token.as_ptr() as usize - line.as_ptr() as usize;
+    line[..offset].chars().count() + 1
+}
+
+fn parse_row(line: &str, line_number: usize) -> Result<Vec<f64>, MatrixError> {
+    let trimmed = line.trim();
+    let tokens: Vec<&str> = if let Some(cells) = trimmed.strip_prefix('|') {
+        // Pipe-table syntax: `| 1 | 2 |`, trailing pipe optional
+        let cells = cells.strip_suffix('|').unwrap_or(cells);
+        cells.split('|').map(str::trim).collect()
+    } else {
+        trimmed.split_whitespace().collect()
+    };
+    tokens
+        .into_iter()
+        .map(|token| {
+            token
+                .parse::<f64>()
+                .map_err(|_| MatrixError::InvalidNumber {
+                    line: line_number,
+                    column: column_of(line, token),
+                    token: token.to_string(),
+                })
+        })
+        .collect()
+}
+
+/// Parses one row per line, with values separated either by whitespace
+/// (`1 2 3`) or by pipes (`| 1 | 2 | 3 |`). Blank lines are skipped.
+impl FromStr for Matrix {
+    type Err = MatrixError;
+
+    fn from_str(s: &str) -> Result<Matrix, MatrixError> {
+        let mut data = Vec::new();
+        let mut columns = None;
+        for (index, line) in s.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let row = parse_row(line, index + 1)?;
+            let expected = *columns.get_or_insert(row.len());
+            if row.len() != expected {
+                return Err(MatrixError::RaggedRow {
+                    line: index + 1,
+                    expected,
+                    found: row.len(),
+                });
+            }
+            data.push(row);
+        }
+        match columns {
+            None | Some(0) => Err(MatrixError::Empty),
+            Some(columns) => Ok(Matrix {
+                rows: data.len(),
+                columns,
+                data,
+            }),
+        }
+    }
+}
+
+impl TryFrom<&str> for Matrix {
+    type Error = MatrixError;
+
+    fn try_from(s: &str) -> Result<Matrix, MatrixError> {
+        s.parse()
+    }
+}
+
+/// Rows are numbered from 1 in `MatrixError::RaggedRow`, as lines are when parsing.
+impl TryFrom<Vec<Vec<f64>>> for Matrix {
+    type Error = MatrixError;
+
+    fn try_from(data: Vec<Vec<f64>>) -> Result<Matrix, MatrixError> {
+        let columns = match data.first() {
+            None => return Err(MatrixError::Empty),
+            Some(row) if row.is_empty() => return Err(MatrixError::Empty),
+            Some(row) => row.len(),
+        };
+        if let Some((index, row)) = data
+            .iter()
+            .enumerate()
+            .find(|(_, row)| row.len() != columns)
+        {
+            return Err(MatrixError::RaggedRow {
+                line: index + 1,
+                expected: columns,
+                found: row.len(),
+            });
+        }
+        Ok(Matrix {
+            rows: data.len(),
+            columns,
+            data,
+        })
+    }
+}
+
+impl From<Matrix> for Vec<Vec<f64>> {
+    fn from(m: Matrix) -> Vec<Vec<f64>> {
+        m.data
+    }
+}
+
+impl<const R: usize, const C: usize> From<FixedMatrix<R, C>> for Matrix {
+    fn from(m: FixedMatrix<R, C>) -> Matrix {
+        Matrix {
+            rows: R,
+            columns: C,
+            data: m.as_array().iter().map(|row| row.to_vec()).collect(),
+        }
+    }
 }
 
 impl PartialEq for Matrix {
@@ -112,9 +425,88 @@ impl PartialEq for Matrix {
     }
 }
 
+impl Mul<&Matrix> for &Matrix {
+    type Output = Result<Matrix, MatrixError>;
+
+    fn mul(self, other: &Matrix) -> Result<Matrix, MatrixError> {
+        if self.columns != other.rows {
+            return Err(MatrixError::DimensionMismatch {
+                left: (self.rows, self.columns),
+                right: (other.rows, other.columns),
+            });
+        }
+        let mut m = Matrix::new(self.rows, other.columns);
+        for i in 0..self.rows {
+            for j in 0..other.columns {
+                m.data[i][j] = (0..self.columns)
+                    .map(|k| self.data[i][k] * other.data[k][j])
+                    .sum();
+            }
+        }
+        Ok(m)
+    }
+}
+
+impl Mul for Matrix {
+    type Output = Result<Matrix, MatrixError>;
+
+    fn mul(self, other: Matrix) -> Result<Matrix, MatrixError> {
+        &self * &other
+    }
+}
+
+impl Mul<&Tuple> for &Matrix {
+    type Output = Result<Tuple, MatrixError>;
+
+    fn mul(self, t: &Tuple) -> Result<Tuple, MatrixError> {
+        self.mul_tuple(t)
+    }
+}
+
+impl Mul<Tuple> for Matrix {
+    type Output = Result<Tuple, MatrixError>;
+
+    fn mul(self, t: Tuple) -> Result<Tuple, MatrixError> {
+        self.mul_tuple(&t)
+    }
+}
+
+impl Mul<&Point> for &Matrix {
+    type Output = Result<Point, MatrixError>;
+
+    fn mul(self, p: &Point) -> Result<Point, MatrixError> {
+        self.mul_tuple(&p.as_tuple()).map(Point::project)
+    }
+}
+
+impl Mul<Point> for Matrix {
+    type Output = Result<Point, MatrixError>;
+
+    fn mul(self, p: Point) -> Result<Point, MatrixError> {
+        &self * &p
+    }
+}
+
+impl Mul<&Vector> for &Matrix {
+    type Output = Result<Vector, MatrixError>;
+
+    fn mul(self, v: &Vector) -> Result<Vector, MatrixError> {
+        self.mul_tuple(&v.as_tuple()).map(Vector::from)
+    }
+}
+
+impl Mul<Vector> for Matrix {
+    type Output = Result<Vector, MatrixError>;
+
+    fn mul(self, v: Vector) -> Result<Vector, MatrixError> {
+        &self * &v
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_approx_eq;
 
     #[test]
     fn test_new() {
@@ -173,6 +565,121 @@ mod tests {
         assert_eq!(m.data[3][3], 2.0);
     }
 
+    #[test]
+    fn test_parse_matrix() {
+        let m: Matrix = "1 2\n3 4".parse().unwrap();
+        assert_eq!(m, Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]));
+        assert_eq!(Matrix::try_from("1 2\n3 4"), Ok(m));
+    }
+
+    #[test]
+    fn test_parse_pipe_table() {
+        let m: Matrix = "
+            | -3 |  5 |  0 |
+            |  1 | -2 | -7 |
+            |  0 |  1 |  1 |
+        "
+        .parse()
+        .unwrap();
+        assert_eq!(m, Matrix::from_string("-3 5 0\n1 -2 -7\n0 1 1"));
+    }
+
+    #[test]
+    fn test_parse_pipe_table_without_trailing_pipe() {
+        let m: Matrix = "| 1.5 | 2\n| 3 | 4".parse().unwrap();
+        assert_eq!(m, Matrix::from_string("1.5 2\n3 4"));
+    }
+
+    #[test]
+    fn test_parse_invalid_number() {
+        assert_eq!(
+            "1 2\n3 x4".parse::<Matrix>(),
+            Err(MatrixError::InvalidNumber {
+                line: 2,
+                column: 3,
+                token: String::from("x4"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_number_in_pipe_table() {
+        assert_eq!(
+            "| 1 | 2 |\n| 3 | 4,5 |".parse::<Matrix>(),
+            Err(MatrixError::InvalidNumber {
+                line: 2,
+                column: 7,
+                token: String::from("4,5"),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_cell_in_pipe_table() {
+        assert_eq!(
+            "| 1 |  |".parse::<Matrix>(),
+            Err(MatrixError::InvalidNumber {
+                line: 1,
+                column: 6,
+                token: String::new(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_ragged_rows() {
+        assert_eq!(
+            "1 2 3\n4 5\n6 7 8".parse::<Matrix>(),
+            Err(MatrixError::RaggedRow {
+                line: 2,
+                expected: 3,
+                found: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn test_parse_empty_string() {
+        assert_eq!("".parse::<Matrix>(), Err(MatrixError::Empty));
+        assert_eq!(" \n\n  ".parse::<Matrix>(), Err(MatrixError::Empty));
+    }
+
+    #[test]
+    fn test_parse_error_message() {
+        let e = "1 2\n3 x4".parse::<Matrix>().unwrap_err();
+        assert_eq!(e.to_string(), "invalid number 'x4' at line 2, column 3");
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid matrix string")]
+    fn test_from_string_panics_on_invalid_input() {
+        Matrix::from_string("1 2\n3");
+    }
+
+    #[test]
+    fn test_try_from_vec() {
+        let m = Matrix::try_from(vec![vec![1.0, 2.0], vec![3.0, 4.0]]).unwrap();
+        assert_eq!(m, Matrix::from_string("1 2\n3 4"));
+    }
+
+    #[test]
+    fn test_try_from_empty_vec() {
+        assert_eq!(Matrix::try_from(vec![]), Err(MatrixError::Empty));
+        assert_eq!(Matrix::try_from(vec![vec![]]), Err(MatrixError::Empty));
+    }
+
+    #[test]
+    fn test_try_from_ragged_vec() {
+        assert_eq!(
+            Matrix::try_from(vec![vec![1.0, 2.0], vec![3.0]]),
+            Err(MatrixError::RaggedRow {
+                line: 2,
+                expected: 2,
+                found: 1,
+            })
+        );
+    }
+
     #[test]
     fn test_create_2x2_matrix_from_vec() {
         let m = Matrix::from_vec(vec![vec![1.0, 2.0], vec![3.0, 4.0]]);
@@ -358,4 +865,329 @@ mod tests {
         assert_eq!(m1, m2);
         assert_ne!(m1, m3);
     }
+
+    #[test]
+    fn test_identity_matrix() {
+        let m = Matrix::identity(3);
+        assert_eq!(m.rows(), 3);
+        assert_eq!(m.columns(), 3);
+        assert_eq!(
+            m.get_rows(),
+            [[1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]]
+        );
+    }
+
+    #[test]
+    fn test_multiply_matrices() {
+        let a = Matrix::from_string("1 2 3 4\n5 6 7 8\n9 8 7 6\n5 4 3 2");
+        let b = Matrix::from_string("-2 1 2 3\n3 2 1 -1\n4 3 6 5\n1 2 7 8");
+        let expected =
+            Matrix::from_string("20 22 50 48\n44 54 114 108\n40 58 110 102\n16 26 46 42");
+        assert_eq!((&a * &b).unwrap(), expected);
+        assert_eq!((a * b).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_multiply_non_square_matrices() {
+        let a = Matrix::from_string("1 2 3\n4 5 6");
+        let b = Matrix::from_string("7 8\n9 10\n11 12");
+        let c = (&a * &b).unwrap();
+        assert_eq!(c, Matrix::from_string("58 64\n139 154"));
+    }
+
+    #[test]
+    fn test_multiply_matrices_with_mismatched_dimensions() {
+        let a = Matrix::from_string("1 2 3\n4 5 6");
+        let b = Matrix::from_string("1 2\n3 4");
+        assert_eq!(
+            &a * &b,
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 3),
+                right: (2, 2),
+            })
+        );
+    }
+
+    #[test]
+    fn test_multiply_matrix_by_identity() {
+        let a = Matrix::from_string("0 1 2 4\n1 2 4 8\n2 4 8 16\n4 8 16 32");
+        assert_eq!((&a * &Matrix::identity(4)).unwrap(), a);
+    }
+
+    #[test]
+    fn test_multiply_matrix_by_tuple() {
+        let a = Matrix::from_string("1 2 3\n2 4 4\n8 6 4");
+        let t = Tuple::new(1.0, 2.0, 3.0);
+        assert_eq!((&a * &t).unwrap(), Tuple::new(14.0, 22.0, 32.0));
+        assert_eq!((a * t).unwrap(), Tuple::new(14.0, 22.0, 32.0));
+    }
+
+    #[test]
+    fn test_multiply_matrix_by_point_and_vector() {
+        let a = Matrix::from_string("1 2 3\n2 4 4\n8 6 4");
+        let p = Point::new(1.0, 2.0, 3.0);
+        let v = Vector::new(1.0, 2.0, 3.0);
+        assert_eq!((&a * &p).unwrap(), Point::new(14.0, 22.0, 32.0));
+        assert_eq!((&a * &v).unwrap(), Vector::new(14.0, 22.0, 32.0));
+    }
+
+    #[test]
+    fn test_multiply_matrix_by_tuple_with_mismatched_dimensions() {
+        let a = Matrix::identity(2);
+        let t = Tuple::new(1.0, 2.0, 3.0);
+        assert_eq!(
+            &a * &t,
+            Err(MatrixError::DimensionMismatch {
+                left: (2, 2),
+                right: (4, 1),
+            })
+        );
+    }
+
+    #[test]
+    fn test_multiply_4x4_matrix_by_homogeneous_tuple() {
+        let a = Matrix::from_string("1 2 3 4\n2 4 4 2\n8 6 4 1\n0 0 0 1");
+        let t = Tuple::homogeneous(1.0, 2.0, 3.0, 1.0);
+        assert_eq!(
+            (&a * &t).unwrap(),
+            Tuple::homogeneous(18.0, 24.0, 33.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_multiply_4x4_matrix_by_point_and_vector() {
+        let a = Matrix::from_string("1 2 3 4\n2 4 4 2\n8 6 4 1\n0 0 0 1");
+        assert_eq!(
+            (&a * &Point::new(1.0, 2.0, 3.0)).unwrap(),
+            Point::new(18.0, 24.0, 33.0)
+        );
+        assert_eq!(
+            (&a * &Vector::new(1.0, 2.0, 3.0)).unwrap(),
+            Vector::new(14.0, 22.0, 32.0)
+        );
+    }
+
+    #[test]
+    fn test_multiply_matrix_by_point_applies_perspective_divide() {
+        let a = Matrix::from_string("1 0 0 0\n0 1 0 0\n0 0 1 0\n0 0 1 0");
+        assert_eq!(
+            (&a * &Point::new(2.0, 4.0, 2.0)).unwrap(),
+            Point::new(1.0, 2.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_transpose_matrix() {
+        let a = Matrix::from_string("0 9 3 0\n9 8 0 8\n1 8 5 3\n0 0 5 8");
+        let expected = Matrix::from_string("0 9 1 0\n9 8 8 0\n3 0 5 5\n0 8 3 8");
+        assert_eq!(a.transpose(), expected);
+    }
+
+    #[test]
+    fn test_transpose_non_square_matrix() {
+        let a = Matrix::from_string("1 2 3\n4 5 6");
+        let t = a.transpose();
+        assert_eq!(t.rows(), 3);
+        assert_eq!(t.columns(), 2);
+        assert_eq!(t, Matrix::from_string("1 4\n2 5\n3 6"));
+    }
+
+    #[test]
+    fn test_transpose_identity_matrix() {
+        assert_eq!(Matrix::identity(4).transpose(), Matrix::identity(4));
+    }
+
+    #[test]
+    fn test_determinant_of_2x2_matrix() {
+        let a = Matrix::from_string("1 5\n-3 2");
+        assert_eq!(a.determinant(), Ok(17.0));
+    }
+
+    #[test]
+    fn test_determinant_of_non_square_matrix() {
+        let a = Matrix::from_string("1 2 3\n4 5 6");
+        assert_eq!(
+            a.determinant(),
+            Err(MatrixError::NotSquare {
+                rows: 2,
+                columns: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_submatrix_of_3x3_matrix() {
+        let a = Matrix::from_string("1 5 0\n-3 2 7\n0 6 -3");
+        assert_eq!(a.submatrix(0, 2), Matrix::from_string("-3 2\n0 6"));
+    }
+
+    #[test]
+    fn test_submatrix_of_4x4_matrix() {
+        let a = Matrix::from_string("-6 1 1 6\n-8 5 8 6\n-1 0 8 2\n-7 1 -1 1");
+        assert_eq!(
+            a.submatrix(2, 1),
+            Matrix::from_string("-6 1 6\n-8 8 6\n-7 -1 1")
+        );
+    }
+
+    #[test]
+    fn test_minor_of_3x3_matrix() {
+        let a = Matrix::from_string("3 5 0\n2 -1 -7\n6 -1 5");
+        let b = a.submatrix(1, 0);
+        assert_eq!(b.determinant(), Ok(25.0));
+        assert_eq!(a.minor(1, 0), Ok(25.0));
+    }
+
+    #[test]
+    fn test_cofactor_of_3x3_matrix() {
+        let a = Matrix::from_string("3 5 0\n2 -1 -7\n6 -1 5");
+        assert_eq!(a.minor(0, 0), Ok(-12.0));
+        assert_eq!(a.cofactor(0, 0), Ok(-12.0));
+        assert_eq!(a.minor(1, 0), Ok(25.0));
+        assert_eq!(a.cofactor(1, 0), Ok(-25.0));
+    }
+
+    #[test]
+    fn test_determinant_of_3x3_matrix() {
+        let a = Matrix::from_string("1 2 6\n-5 8 -4\n2 6 4");
+        assert_eq!(a.cofactor(0, 0), Ok(56.0));
+        assert_eq!(a.cofactor(0, 1), Ok(12.0));
+        assert_eq!(a.cofactor(0, 2), Ok(-46.0));
+        assert_eq!(a.determinant(), Ok(-196.0));
+    }
+
+    #[test]
+    fn test_determinant_of_4x4_matrix() {
+        let a = Matrix::from_string("-2 -8 3 5\n-3 1 7 3\n1 2 -9 6\n-6 7 7 -9");
+        assert_eq!(a.cofactor(0, 0), Ok(690.0));
+        assert_eq!(a.cofactor(0, 1), Ok(447.0));
+        assert_eq!(a.cofactor(0, 2), Ok(210.0));
+        assert_eq!(a.cofactor(0, 3), Ok(51.0));
+        assert_eq!(a.determinant(), Ok(-4071.0));
+    }
+
+    #[test]
+    fn test_determinant_of_5x5_matrix() {
+        let a = Matrix::from_string("2 0 0 0 0\n0 3 0 0 0\n0 0 4 0 0\n0 0 0 5 0\n1 2 3 4 6");
+        assert_eq!(a.determinant(), Ok(720.0));
+    }
+
+    #[test]
+    fn test_invertible_matrix() {
+        let a = Matrix::from_string("6 4 4 4\n5 5 7 6\n4 -9 3 -7\n9 1 7 -6");
+        assert_eq!(a.determinant(), Ok(-2120.0));
+        assert!(a.is_invertible());
+    }
+
+    #[test]
+    fn test_non_invertible_matrix() {
+        let a = Matrix::from_string("-4 2 -2 -3\n9 6 2 6\n0 -5 1 -5\n0 0 0 0");
+        assert_eq!(a.determinant(), Ok(0.0));
+        assert!(!a.is_invertible());
+        assert_eq!(a.inverse(), Err(MatrixError::NotInvertible));
+    }
+
+    #[test]
+    fn test_non_finite_matrix_is_not_invertible() {
+        for value in [f64::NAN, f64::INFINITY] {
+            let a = Matrix::from_vec(vec![vec![value, 0.0], vec![0.0, 1.0]]);
+            assert!(!a.is_invertible());
+            assert_eq!(a.inverse(), Err(MatrixError::NotInvertible));
+        }
+    }
+
+    #[test]
+    fn test_non_square_matrix_is_not_invertible() {
+        let a = Matrix::from_string("1 2 3\n4 5 6");
+        assert!(!a.is_invertible());
+        assert_eq!(
+            a.inverse(),
+            Err(MatrixError::NotSquare {
+                rows: 2,
+                columns: 3,
+            })
+        );
+    }
+
+    #[test]
+    fn test_inverse_of_matrix() {
+        let a = Matrix::from_string("-5 2 6 -8\n1 -5 1 8\n7 7 -6 -7\n1 -3 7 4");
+        let b = a.inverse().unwrap();
+        assert_eq!(a.determinant(), Ok(532.0));
+        assert_eq!(a.cofactor(2, 3), Ok(-160.0));
+        assert_approx_eq!(b.get(3, 2), -160.0 / 532.0, 0.00001);
+        assert_eq!(a.cofactor(3, 2), Ok(105.0));
+        assert_approx_eq!(b.get(2, 3), 105.0 / 532.0, 0.00001);
+        assert_approx_eq!(
+            b,
+            Matrix::from_string(
+                "0.21805 0.45113 0.24060 -0.04511\n\
+                 -0.80827 -1.45677 -0.44361 0.52068\n\
+                 -0.07895 -0.22368 -0.05263 0.19737\n\
+                 -0.52256 -0.81391 -0.30075 0.30639",
+            ),
+            0.00001
+        );
+    }
+
+    #[test]
+    fn test_inverse_of_another_matrix() {
+        let a = Matrix::from_string("8 -5 9 2\n7 5 6 1\n-6 0 9 6\n-3 0 -9 -4");
+        assert_approx_eq!(
+            a.inverse().unwrap(),
+            Matrix::from_string(
+                "-0.15385 -0.15385 -0.28205 -0.53846\n\
+                 -0.07692 0.12308 0.02564 0.03077\n\
+                 0.35897 0.35897 0.43590 0.92308\n\
+                 -0.69231 -0.69231 -0.76923 -1.92308",
+            ),
+            0.00001
+        );
+    }
+
+    #[test]
+    fn test_inverse_of_a_third_matrix() {
+        let a = Matrix::from_string("9 3 0 9\n-5 -2 -6 -3\n-4 9 6 4\n-7 6 6 2");
+        assert_approx_eq!(
+            a.inverse().unwrap(),
+            Matrix::from_string(
+                "-0.04074 -0.07778 0.14444 -0.22222\n\
+                 -0.07778 0.03333 0.36667 -0.33333\n\
+                 -0.02901 -0.14630 -0.10926 0.12963\n\
+                 0.17778 0.06667 -0.26667 0.33333",
+            ),
+            0.00001
+        );
+    }
+
+    #[test]
+    fn test_multiply_product_by_inverse() {
+        let a = Matrix::from_string("3 -9 7 3\n3 -8 2 -9\n-4 4 4 1\n-6 5 -1 1");
+        let b = Matrix::from_string("8 2 2 2\n3 -1 7 0\n7 0 5 4\n6 -2 0 5");
+        let c = (&a * &b).unwrap();
+        assert_approx_eq!((&c * &b.inverse().unwrap()).unwrap(), a, 0.00001);
+    }
+
+    #[test]
+    fn test_values_are_double_precision_whatever_the_coordinate_type() {
+        let m = Matrix::from_vec(vec![vec![1.0 + 1e-12, 0.0], vec![0.0, 1.0]]);
+        assert_ne!(m.get(0, 0), 1.0);
+        assert_ne!(m.inverse().unwrap().get(0, 0), 1.0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let m = Matrix::from_string("1 2\n3 4.5");
+        let json = serde_json::to_string(&m).unwrap();
+        assert_eq!(json, "[[1.0,2.0],[3.0,4.5]]");
+        assert_eq!(serde_json::from_str::<Matrix>(&json).unwrap(), m);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_rejects_ragged_rows() {
+        let error = serde_json::from_str::<Matrix>("[[1.0,2.0],[3.0]]").unwrap_err();
+        assert!(error.to_string().contains("line 2"), "{error}");
+    }
 }
@@ -0,0 +1,261 @@
+use crate::fixed_matrix::Matrix4;
+use crate::matrix::{Matrix, MatrixError};
+use crate::tuple::{forward_neg, CoordValue, Tuple};
+use crate::vector::Vector;
+use std::ops;
+
+/// A surface normal, always of unit length.
+///
+/// Unlike vectors, normals must be transformed by the inverse transpose of a
+/// matrix to stay perpendicular to the transformed surface, which is what
+/// [`Normal::transform`] and `&Matrix * &Normal` do.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "crate::tuple::Coordinates", into = "crate::tuple::Coordinates")
+)]
+pub struct Normal {
+    vector: Vector,
+}
+
+impl Normal {
+    /// Builds the normal pointing along `(x, y, z)`, normalizing it. The zero
+    /// vector has no direction and yields NaN components.
+    pub fn new(x: CoordValue, y: CoordValue, z: CoordValue) -> Normal {
+        Normal::from_vector(&Vector::new(x, y, z))
+    }
+
+    pub fn from_vector(v: &Vector) -> Normal {
+        Normal {
+            vector: v.normalize(),
+        }
+    }
+
+    pub fn as_vector(&self) -> Vector {
+        self.vector.clone()
+    }
+
+    pub fn as_tuple(&self) -> Tuple {
+        self.vector.as_tuple()
+    }
+
+    pub fn neg(&self) -> Normal {
+        Normal {
+            vector: self.vector.neg(),
+        }
+    }
+
+    pub fn dot(&self, other: &Vector) -> CoordValue {
+        self.vector.dot(other)
+    }
+
+    /// Transforms this normal by `m`, using the inverse transpose of `m`.
+    pub fn transform(&self, m: &Matrix) -> Result<Normal, MatrixError> {
+        self.transform_by_inverse(&m.inverse()?)
+    }
+
+    /// Same as [`Normal::transform`] when the inverse of the transformation is
+    /// already at hand, e.g. cached alongside it.
+    pub fn transform_by_inverse(&self, inverse: &Matrix) -> Result<Normal, MatrixError> {
+        (&inverse.transpose() * &self.vector).map(|v| Normal::from_vector(&v))
+    }
+
+    /// Same as [`Normal::transform_by_inverse`] for a fixed 4x4 inverse, which
+    /// cannot fail.
+    pub fn transform_by_fixed_inverse(&self, inverse: &Matrix4) -> Normal {
+        Normal::from_vector(&(&inverse.transpose() * &self.vector))
+    }
+
+    pub fn format(&self) -> String {
+        self.vector.format()
+    }
+
+    pub fn x(&self) -> CoordValue {
+        self.vector.x()
+    }
+
+    pub fn y(&self) -> CoordValue {
+        self.vector.y()
+    }
+
+    pub fn z(&self) -> CoordValue {
+        self.vector.z()
+    }
+}
+
+/// Deserialized normals are normalized again.
+#[cfg(feature = "serde")]
+impl From<crate::tuple::Coordinates> for Normal {
+    fn from(c: crate::tuple::Coordinates) -> Normal {
+        Normal::from_vector(&Vector::from_array(c.as_array()))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Normal> for crate::tuple::Coordinates {
+    fn from(n: Normal) -> crate::tuple::Coordinates {
+        crate::tuple::Coordinates::new(n.vector.x(), n.vector.y(), n.vector.z())
+    }
+}
+
+impl From<Vector> for Normal {
+    fn from(v: Vector) -> Normal {
+        Normal::from_vector(&v)
+    }
+}
+
+impl From<Normal> for Vector {
+    fn from(n: Normal) -> Vector {
+        n.vector
+    }
+}
+
+impl ops::Neg for &Normal {
+    type Output = Normal;
+
+    fn neg(self) -> Normal {
+        Normal::neg(self)
+    }
+}
+
+forward_neg!(Normal);
+
+impl ops::Mul<&Normal> for &Matrix {
+    type Output = Result<Normal, MatrixError>;
+
+    fn mul(self, n: &Normal) -> Result<Normal, MatrixError> {
+        n.transform(self)
+    }
+}
+
+impl ops::Mul<Normal> for Matrix {
+    type Output = Result<Normal, MatrixError>;
+
+    fn mul(self, n: Normal) -> Result<Normal, MatrixError> {
+        n.transform(&self)
+    }
+}
+
+impl ops::Mul<&Normal> for &Matrix4 {
+    type Output = Result<Normal, MatrixError>;
+
+    fn mul(self, n: &Normal) -> Result<Normal, MatrixError> {
+        Ok(n.transform_by_fixed_inverse(&self.inverse()?))
+    }
+}
+
+impl ops::Mul<Normal> for Matrix4 {
+    type Output = Result<Normal, MatrixError>;
+
+    fn mul(self, n: Normal) -> Result<Normal, MatrixError> {
+        &self * &n
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::{rotation_z, scaling, translation};
+    use crate::{assert_approx_eq, assert_approx_ne};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_new_normalizes() {
+        let n = Normal::new(4.0, 0.0, 0.0);
+        assert_eq!(n, Normal::new(1.0, 0.0, 0.0));
+        let n = Normal::new(1.0, 2.0, 3.0);
+        assert_approx_eq!(n.as_vector().magnitude(), 1.0);
+        assert_approx_eq!(n.as_vector(), Vector::new(1.0, 2.0, 3.0).normalize());
+    }
+
+    #[test]
+    fn test_vector_round_trip() {
+        let n = Normal::from(Vector::new(0.0, 3.0, 4.0));
+        let v: Vector = n.clone().into();
+        assert_approx_eq!(v, Vector::new(0.0, 0.6, 0.8));
+        assert!(n.as_tuple().is_vector());
+    }
+
+    #[test]
+    fn test_neg() {
+        let n = Normal::new(0.0, 1.0, 0.0);
+        assert_eq!(-&n, Normal::new(0.0, -1.0, 0.0));
+        assert_eq!(-n, Normal::new(0.0, -1.0, 0.0));
+    }
+
+    #[test]
+    fn test_translation_does_not_affect_normal() {
+        let n = Normal::new(0.0, 1.0, 0.0);
+        let m = translation(1.0, 2.0, 3.0);
+        assert_approx_eq!(n.transform(&m).unwrap().as_vector(), n.as_vector());
+    }
+
+    #[test]
+    fn test_non_uniform_scale_uses_inverse_transpose() {
+        let half = CoordValue::sqrt(2.0) / 2.0;
+        let n = Normal::new(half, half, 0.0);
+        let m = scaling(2.0, 1.0, 1.0);
+        // A plain vector transform would tilt the normal towards x
+        let wrong = Normal::from((&m * &n.as_vector()).unwrap());
+        let right = (&m * &n).unwrap();
+        assert_approx_eq!(right.as_vector(), Vector::new(1.0, 2.0, 0.0).normalize());
+        assert_approx_ne!(wrong.as_vector(), right.as_vector());
+    }
+
+    #[test]
+    fn test_stays_perpendicular_to_transformed_surface() {
+        let m = (&scaling(1.0, 0.5, 1.0) * &rotation_z(PI / 5.0)).unwrap();
+        let n = Normal::new(0.0, 1.0, -1.0);
+        let tangent = Vector::new(0.0, 1.0, 1.0);
+        assert_approx_eq!(n.dot(&tangent), 0.0);
+        let transformed_tangent = (&m * &tangent).unwrap();
+        assert_approx_eq!((&m * &n).unwrap().dot(&transformed_tangent), 0.0);
+    }
+
+    #[test]
+    fn test_transform_by_inverse() {
+        let m = scaling(2.0, 1.0, 1.0);
+        let n = Normal::new(1.0, 1.0, 0.0);
+        assert_approx_eq!(
+            n.transform_by_inverse(&m.inverse().unwrap())
+                .unwrap()
+                .as_vector(),
+            n.transform(&m).unwrap().as_vector()
+        );
+    }
+
+    #[test]
+    fn test_fixed_matrix() {
+        let m = Matrix4::try_from(scaling(2.0, 1.0, 1.0)).unwrap();
+        let n = Normal::new(1.0, 1.0, 0.0);
+        assert_approx_eq!(
+            (&m * &n).unwrap().as_vector(),
+            Vector::new(1.0, 2.0, 0.0).normalize()
+        );
+        assert_approx_eq!(
+            n.transform_by_fixed_inverse(&m.inverse().unwrap()),
+            (&m * &n).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_singular_matrix() {
+        let m = scaling(0.0, 1.0, 1.0);
+        assert_eq!(
+            Normal::new(1.0, 0.0, 0.0).transform(&m),
+            Err(MatrixError::NotInvertible)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let n = Normal::new(0.0, 3.0, -4.0);
+        let json = serde_json::to_string(&n).unwrap();
+        assert_eq!(json, r#"{"x":0.0,"y":0.6,"z":-0.8}"#);
+        assert_eq!(serde_json::from_str::<Normal>(&json).unwrap(), n);
+        let back: Normal = serde_json::from_str(r#"{"x":0.0,"y":0.0,"z":2.0}"#).unwrap();
+        assert_eq!(back, Normal::new(0.0, 0.0, 1.0));
+    }
+}
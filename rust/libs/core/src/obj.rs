@@ -0,0 +1,482 @@
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::shapes::{Group, Shape, SmoothTriangle, Triangle};
+use crate::tuple::CoordValue;
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjError {
+    MissingValues {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    InvalidNumber {
+        line: usize,
+        token: String,
+    },
+    InvalidIndex {
+        line: usize,
+        token: String,
+    },
+    MissingVertex {
+        line: usize,
+        index: isize,
+    },
+    MissingNormal {
+        line: usize,
+        index: isize,
+    },
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ObjError::MissingValues {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expected at least {expected} values at line {line}, found {found}"
+            ),
+            ObjError::InvalidNumber { line, token } => {
+                write!(f, "invalid number '{token}' at line {line}")
+            }
+            ObjError::InvalidIndex { line, token } => {
+                write!(f, "invalid face index '{token}' at line {line}")
+            }
+            ObjError::MissingVertex { line, index } => {
+                write!(f, "no vertex {index} for the face at line {line}")
+            }
+            ObjError::MissingNormal { line, index } => {
+                write!(f, "no normal {index} for the face at line {line}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ObjError {}
+
+/// The faces that follow a `g` statement, as triangles.
+#[derive(Debug)]
+pub struct ObjGroup {
+    pub name: String,
+    pub triangles: Vec<Box<dyn Shape>>,
+}
+
+/// A line that was skipped because its statement is not supported, numbered
+/// from 1.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IgnoredLine {
+    pub line: usize,
+    pub content: String,
+}
+
+/// The geometry of a Wavefront OBJ file.
+///
+/// Only vertices (`v`), vertex normals (`vn`), faces (`f`) and groups (`g`)
+/// are read. Polygons are split into a fan of triangles, which are smooth
+/// when every corner has a normal. Comments and blank lines are skipped
+/// silently; any other statement is recorded in `ignored`.
+#[derive(Debug, Default)]
+pub struct ObjFile {
+    pub vertices: Vec<Point>,
+    pub normals: Vec<Normal>,
+    /// Faces that appear before any `g` statement.
+    pub default_group: Vec<Box<dyn Shape>>,
+    /// Named groups in order of first appearance; faces of a group that is
+    /// named again are added to it.
+    pub groups: Vec<ObjGroup>,
+    pub ignored: Vec<IgnoredLine>,
+}
+
+fn parse_values(
+    values: &[&str],
+    line: usize,
+) -> Result<(CoordValue, CoordValue, CoordValue), ObjError> {
+    if values.len() < 3 {
+        return Err(ObjError::MissingValues {
+            line,
+            expected: 3,
+            found: values.len(),
+        });
+    }
+    let mut parsed = [0.0; 3];
+    for (value, token) in parsed.iter_mut().zip(values) {
+        *value = token.parse().map_err(|_| ObjError::InvalidNumber {
+            line,
+            token: token.to_string(),
+        })?;
+    }
+    Ok((parsed[0], parsed[1], parsed[2]))
+}
+
+/// Resolves a 1-based index, or a negative one counting back from the last
+/// element read so far, into `items`.
+fn resolve<T: Clone>(items: &[T], index: isize) -> Option<T> {
+    let position = if index > 0 {
+        index - 1
+    } else {
+        items.len() as isize + index
+    };
+    usize::try_from(position)
+        .ok()
+        .and_then(|position| items.get(position))
+        .cloned()
+}
+
+fn parse_index(token: &str, line: usize) -> Result<isize, ObjError> {
+    match token.parse() {
+        Ok(0) | Err(_) => Err(ObjError::InvalidIndex {
+            line,
+            token: token.to_string(),
+        }),
+        Ok(index) => Ok(index),
+    }
+}
+
+impl ObjFile {
+    pub fn group(&self, name: &str) -> Option<&ObjGroup> {
+        self.groups.iter().find(|group| group.name == name)
+    }
+
+    /// Number of triangles across all groups.
+    pub fn triangle_count(&self) -> usize {
+        self.default_group.len()
+            + self
+                .groups
+                .iter()
+                .map(|group| group.triangles.len())
+                .sum::<usize>()
+    }
+
+    /// All the triangles in a single group, ready to be transformed and added
+    /// to a world. Named groups become subgroups, after the faces of the
+    /// default group.
+    pub fn into_group(self) -> Group {
+        let mut group = Group::new();
+        for triangle in self.default_group {
+            group.add_boxed_child(triangle);
+        }
+        for named in self.groups {
+            let mut subgroup = Group::new();
+            for triangle in named.triangles {
+                subgroup.add_boxed_child(triangle);
+            }
+            group.add_child(subgroup);
+        }
+        group
+    }
+
+    /// Corner of a face given as `v`, `v/vt`, `v//vn` or `v/vt/vn`. Texture
+    /// coordinates are not supported and skipped.
+    fn parse_corner(&self, token: &str, line: usize) -> Result<(Point, Option<Normal>), ObjError> {
+        let mut parts = token.split('/');
+        let vertex = parse_index(parts.next().unwrap_or_default(), line)?;
+        let vertex = resolve(&self.vertices, vertex).ok_or(ObjError::MissingVertex {
+            line,
+            index: vertex,
+        })?;
+        let normal = match parts.nth(1) {
+            Some(index) if !index.is_empty() => {
+                let index = parse_index(index, line)?;
+                Some(resolve(&self.normals, index).ok_or(ObjError::MissingNormal { line, index })?)
+            }
+            _ => None,
+        };
+        Ok((vertex, normal))
+    }
+
+    fn parse_face(&self, corners: &[&str], line: usize) -> Result<Vec<Box<dyn Shape>>, ObjError> {
+        if corners.len() < 3 {
+            return Err(ObjError::MissingValues {
+                line,
+                expected: 3,
+                found: corners.len(),
+            });
+        }
+        let corners = corners
+            .iter()
+            .map(|token| self.parse_corner(token, line))
+            .collect::<Result<Vec<_>, _>>()?;
+        let (first, rest) = corners.split_first().unwrap();
+        Ok(rest
+            .windows(2)
+            .map(|pair| -> Box<dyn Shape> {
+                let (p1, n1) = first.clone();
+                let (p2, n2) = pair[0].clone();
+                let (p3, n3) = pair[1].clone();
+                match (n1, n2, n3) {
+                    (Some(n1), Some(n2), Some(n3)) => {
+                        Box::new(SmoothTriangle::new(p1, p2, p3, n1, n2, n3))
+                    }
+                    _ => Box::new(Triangle::new(p1, p2, p3)),
+                }
+            })
+            .collect())
+    }
+}
+
+impl FromStr for ObjFile {
+    type Err = ObjError;
+
+    fn from_str(s: &str) -> Result<ObjFile, ObjError> {
+        let mut obj = ObjFile::default();
+        // Index into `groups` of the group faces go to, if any
+        let mut current: Option<usize> = None;
+        for (index, content) in s.lines().enumerate() {
+            let line = index + 1;
+            let tokens: Vec<&str> = content.split_whitespace().collect();
+            match tokens.split_first() {
+                None => {}
+                Some((keyword, _)) if keyword.starts_with('#') => {}
+                Some((&"v", values)) => {
+                    obj.vertices
+                        .push(Point::from_tuple(parse_values(values, line)?));
+                }
+                Some((&"vn", values)) => {
+                    let (x, y, z) = parse_values(values, line)?;
+                    obj.normals.push(Normal::new(x, y, z));
+                }
+                Some((&"f", corners)) => {
+                    let triangles = obj.parse_face(corners, line)?;
+                    match current {
+                        Some(group) => obj.groups[group].triangles.extend(triangles),
+                        None => obj.default_group.extend(triangles),
+                    }
+                }
+                Some((&"g", names)) => {
+                    let name = names.join(" ");
+                    current = Some(
+                        match obj.groups.iter().position(|group| group.name == name) {
+                            Some(group) => group,
+                            None => {
+                                obj.groups.push(ObjGroup {
+                                    name,
+                                    triangles: Vec::new(),
+                                });
+                                obj.groups.len() - 1
+                            }
+                        },
+                    );
+                }
+                Some(_) => obj.ignored.push(IgnoredLine {
+                    line,
+                    content: content.to_string(),
+                }),
+            }
+        }
+        Ok(obj)
+    }
+}
+
+impl TryFrom<&str> for ObjFile {
+    type Error = ObjError;
+
+    fn try_from(s: &str) -> Result<ObjFile, ObjError> {
+        s.parse()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ray::Ray;
+    use crate::transform::translation;
+    use crate::vector::Vector;
+
+    #[test]
+    fn test_ignores_unrecognized_lines() {
+        let obj: ObjFile = "There was a young lady named Bright\n\
+                            who traveled much faster than light.\n\
+                            \n\
+                            # A comment\n\
+                            She set out one day\n"
+            .parse()
+            .unwrap();
+        assert_eq!(obj.triangle_count(), 0);
+        let lines: Vec<usize> = obj.ignored.iter().map(|ignored| ignored.line).collect();
+        assert_eq!(lines, vec![1, 2, 5]);
+        assert_eq!(obj.ignored[2].content, "She set out one day");
+    }
+
+    #[test]
+    fn test_vertices() {
+        let obj: ObjFile = "v -1 1 0\nv -1.0000 0.5000 0.0000\nv 1 0 0\nv 1 1 0\n"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            obj.vertices,
+            vec![
+                Point::new(-1.0, 1.0, 0.0),
+                Point::new(-1.0, 0.5, 0.0),
+                Point::new(1.0, 0.0, 0.0),
+                Point::new(1.0, 1.0, 0.0),
+            ]
+        );
+        assert!(obj.ignored.is_empty());
+    }
+
+    #[test]
+    fn test_vertex_normals() {
+        let obj: ObjFile = "vn 0 0 1\nvn 0.707 0 -0.707\nvn 1 2 3\n".parse().unwrap();
+        assert_eq!(
+            obj.normals,
+            vec![
+                Normal::new(0.0, 0.0, 1.0),
+                Normal::new(0.707, 0.0, -0.707),
+                Normal::new(1.0, 2.0, 3.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_triangle_faces() {
+        let obj: ObjFile = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\nf 1 2 3\nf 1 3 4\n"
+            .parse()
+            .unwrap();
+        assert_eq!(obj.default_group.len(), 2);
+        // A ray through the second triangle only
+        let r = Ray::new(Point::new(0.5, 0.75, -1.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(obj.default_group[0].intersect(&r).is_empty());
+        assert_eq!(obj.default_group[1].intersect(&r)[0].t, 1.0);
+    }
+
+    #[test]
+    fn test_polygons_are_triangulated_as_fans() {
+        let obj: ObjFile = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\nv 0 2 0\n\nf 1 2 3 4 5\n"
+            .parse()
+            .unwrap();
+        assert_eq!(obj.default_group.len(), 3);
+        // Each triangle of the fan shares the first vertex
+        for (triangle, corners) in obj.default_group.iter().zip([(2, 3), (3, 4), (4, 5)]) {
+            let expected = Triangle::new(
+                obj.vertices[0].clone(),
+                obj.vertices[corners.0 - 1].clone(),
+                obj.vertices[corners.1 - 1].clone(),
+            );
+            assert_eq!(format!("{triangle:?}"), format!("{expected:?}"));
+        }
+    }
+
+    #[test]
+    fn test_named_groups() {
+        let obj: ObjFile = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+                            g FirstGroup\nf 1 2 3\ng SecondGroup\nf 1 3 4\n\
+                            g FirstGroup\nf 2 3 4\n"
+            .parse()
+            .unwrap();
+        assert!(obj.default_group.is_empty());
+        let names: Vec<&str> = obj.groups.iter().map(|group| group.name.as_str()).collect();
+        assert_eq!(names, vec!["FirstGroup", "SecondGroup"]);
+        assert_eq!(obj.group("FirstGroup").unwrap().triangles.len(), 2);
+        assert_eq!(obj.group("SecondGroup").unwrap().triangles.len(), 1);
+        assert!(obj.group("ThirdGroup").is_none());
+        assert_eq!(obj.triangle_count(), 3);
+    }
+
+    #[test]
+    fn test_into_group() {
+        let obj: ObjFile = "v -1 1 0\nv -1 0 0\nv 1 0 0\nv 1 1 0\n\
+                            f 1 2 3\ng FirstGroup\nf 1 2 3\ng SecondGroup\nf 1 3 4\n"
+            .parse()
+            .unwrap();
+        let mut group = obj.into_group();
+        assert_eq!(group.children().len(), 3);
+        // The subgroups follow the group they are in
+        group.set_transform(translation(0.0, 0.0, 10.0)).unwrap();
+        let r = Ray::new(Point::new(0.5, 0.75, -1.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = group.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 11.0);
+        assert_eq!(
+            xs[0].object.normal_at(&Point::new(0.5, 0.75, 10.0)),
+            Normal::new(0.0, 0.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn test_faces_with_normals_are_smooth() {
+        let obj: ObjFile = "v 0 1 0\nv -1 0 0\nv 1 0 0\n\
+                            vn -1 0 0\nvn 1 0 0\nvn 0 1 0\n\
+                            f 1//3 2//1 3//2\nf 1/0/3 2/102/1 3/14/2\n"
+            .parse()
+            .unwrap();
+        let expected = SmoothTriangle::new(
+            obj.vertices[0].clone(),
+            obj.vertices[1].clone(),
+            obj.vertices[2].clone(),
+            obj.normals[2].clone(),
+            obj.normals[0].clone(),
+            obj.normals[1].clone(),
+        );
+        assert_eq!(obj.default_group.len(), 2);
+        for triangle in &obj.default_group {
+            assert_eq!(format!("{triangle:?}"), format!("{expected:?}"));
+        }
+    }
+
+    #[test]
+    fn test_negative_indices_count_from_end() {
+        let obj: ObjFile = "v 0 1 0\nv -1 0 0\nv 1 0 0\nf -3 -2 -1\n".parse().unwrap();
+        let expected = Triangle::new(
+            obj.vertices[0].clone(),
+            obj.vertices[1].clone(),
+            obj.vertices[2].clone(),
+        );
+        assert_eq!(
+            format!("{:?}", obj.default_group[0]),
+            format!("{expected:?}")
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(
+            ObjFile::try_from("v 1 2\n").unwrap_err(),
+            ObjError::MissingValues {
+                line: 1,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            ObjFile::try_from("\nvn 1 x 3\n").unwrap_err(),
+            ObjError::InvalidNumber {
+                line: 2,
+                token: "x".to_string()
+            }
+        );
+        assert_eq!(
+            ObjFile::try_from("v 0 0 0\nv 1 0 0\nf 1 2\n").unwrap_err(),
+            ObjError::MissingValues {
+                line: 3,
+                expected: 3,
+                found: 2
+            }
+        );
+        assert_eq!(
+            ObjFile::try_from("v 0 0 0\nv 1 0 0\nf 1 2 0\n").unwrap_err(),
+            ObjError::InvalidIndex {
+                line: 3,
+                token: "0".to_string()
+            }
+        );
+        assert_eq!(
+            ObjFile::try_from("v 0 0 0\nv 1 0 0\nf 1 2 3\n").unwrap_err(),
+            ObjError::MissingVertex { line: 3, index: 3 }
+        );
+        assert_eq!(
+            ObjFile::try_from("v 0 0 0\nv 1 0 0\nv 0 1 0\nf 1//1 2 3\n").unwrap_err(),
+            ObjError::MissingNormal { line: 4, index: 1 }
+        );
+    }
+
+    #[test]
+    fn test_error_display() {
+        let error = ObjError::MissingVertex { line: 3, index: 7 };
+        assert_eq!(error.to_string(), "no vertex 7 for the face at line 3");
+    }
+}
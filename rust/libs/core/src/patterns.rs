@@ -0,0 +1,159 @@
+mod blended;
+mod checker;
+mod gradient;
+mod perturbed;
+mod ring;
+mod solid;
+mod stripe;
+#[cfg(test)]
+mod test_pattern;
+
+pub use blended::Blended;
+pub use checker::Checker;
+pub use gradient::Gradient;
+pub use perturbed::Perturbed;
+pub use ring::Ring;
+pub use solid::Solid;
+pub use stripe::Stripe;
+#[cfg(test)]
+pub(crate) use test_pattern::TestPattern;
+
+use crate::color::Color;
+use crate::fixed_matrix::Matrix4;
+use crate::matrix::{Matrix, MatrixError};
+use crate::point::Point;
+use crate::shapes::Shape;
+use crate::tuple::CoordValue;
+use std::fmt;
+
+/// State shared by every pattern: its transform, kept together with its
+/// inverse.
+#[derive(Debug, Clone)]
+pub struct PatternProperties {
+    transform: Matrix4,
+    inverse_transform: Matrix4,
+}
+
+impl PatternProperties {
+    pub fn new() -> PatternProperties {
+        PatternProperties {
+            transform: Matrix4::identity(),
+            inverse_transform: Matrix4::identity(),
+        }
+    }
+}
+
+impl Default for PatternProperties {
+    fn default() -> Self {
+        PatternProperties::new()
+    }
+}
+
+/// A color that varies over space. Implementors only provide
+/// [`Pattern::pattern_at`], in pattern space; the provided methods move points
+/// there from the space of the object the pattern is on (or of the pattern it
+/// is nested in) and from world space.
+///
+/// Patterns are shared between materials, and so must be `Send + Sync`.
+pub trait Pattern: fmt::Debug + Send + Sync {
+    fn properties(&self) -> &PatternProperties;
+
+    fn properties_mut(&mut self) -> &mut PatternProperties;
+
+    /// Color at a pattern-space point.
+    fn pattern_at(&self, point: &Point) -> Color;
+
+    fn transform(&self) -> &Matrix4 {
+        &self.properties().transform
+    }
+
+    fn inverse_transform(&self) -> &Matrix4 {
+        &self.properties().inverse_transform
+    }
+
+    /// Sets the pattern-to-object transformation, which must be an
+    /// invertible 4x4 matrix.
+    fn set_transform(&mut self, transform: Matrix) -> Result<(), MatrixError> {
+        let transform = Matrix4::try_from(transform)?;
+        let inverse_transform = transform.inverse()?;
+        let properties = self.properties_mut();
+        properties.transform = transform;
+        properties.inverse_transform = inverse_transform;
+        Ok(())
+    }
+
+    /// Color at a point in the space of the object the pattern is on, or of
+    /// the pattern it is nested in.
+    fn color_at(&self, point: &Point) -> Color {
+        self.pattern_at(&(self.inverse_transform() * point))
+    }
+
+    /// Color at a world-space point on `object`.
+    fn color_at_object(&self, object: &dyn Shape, point: &Point) -> Color {
+        self.color_at(&object.world_to_object(point))
+    }
+}
+
+/// Declares a pattern choosing between, or mixing, two other patterns `a` and
+/// `b`, with the constructors and accessors such patterns share. The pattern
+/// still has to implement [`Pattern`] itself.
+macro_rules! two_pattern_struct {
+    ($(#[$attr:meta])* $name:ident) => {
+        $(#[$attr])*
+        #[derive(Debug)]
+        pub struct $name {
+            properties: $crate::patterns::PatternProperties,
+            a: Box<dyn $crate::patterns::Pattern>,
+            b: Box<dyn $crate::patterns::Pattern>,
+        }
+
+        impl $name {
+            pub fn new(a: $crate::color::Color, b: $crate::color::Color) -> $name {
+                $name::nested(
+                    $crate::patterns::Solid::new(a),
+                    $crate::patterns::Solid::new(b),
+                )
+            }
+
+            /// The same pattern filled with other patterns instead of plain
+            /// colors.
+            pub fn nested<A, B>(a: A, b: B) -> $name
+            where
+                A: $crate::patterns::Pattern + 'static,
+                B: $crate::patterns::Pattern + 'static,
+            {
+                $name {
+                    properties: $crate::patterns::PatternProperties::new(),
+                    a: Box::new(a),
+                    b: Box::new(b),
+                }
+            }
+
+            pub fn a(&self) -> &dyn $crate::patterns::Pattern {
+                self.a.as_ref()
+            }
+
+            pub fn b(&self) -> &dyn $crate::patterns::Pattern {
+                self.b.as_ref()
+            }
+        }
+    };
+}
+
+pub(crate) use two_pattern_struct;
+
+/// Whether the integer part of `value` is even, which alternating patterns
+/// use to pick a color.
+fn is_even(value: CoordValue) -> bool {
+    (value.floor() as i64).rem_euclid(2) == 0
+}
+
+#[cfg(test)]
+pub(crate) fn white() -> Color {
+    Color::new(1.0, 1.0, 1.0)
+}
+
+#[cfg(test)]
+pub(crate) fn black() -> Color {
+    Color::new(0.0, 0.0, 0.0)
+}
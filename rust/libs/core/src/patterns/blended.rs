@@ -0,0 +1,65 @@
+use crate::color::Color;
+use crate::patterns::{Pattern, PatternProperties};
+use crate::point::Point;
+
+/// The average of two patterns, each evaluated with its own transform.
+#[derive(Debug)]
+pub struct Blended {
+    properties: PatternProperties,
+    a: Box<dyn Pattern>,
+    b: Box<dyn Pattern>,
+}
+
+impl Blended {
+    pub fn new<A: Pattern + 'static, B: Pattern + 'static>(a: A, b: B) -> Blended {
+        Blended {
+            properties: PatternProperties::new(),
+            a: Box::new(a),
+            b: Box::new(b),
+        }
+    }
+
+    pub fn a(&self) -> &dyn Pattern {
+        self.a.as_ref()
+    }
+
+    pub fn b(&self) -> &dyn Pattern {
+        self.b.as_ref()
+    }
+}
+
+impl Pattern for Blended {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        &(&self.a.color_at(point) + &self.b.color_at(point)) / 2.0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{black, white, Stripe};
+    use crate::transform::rotation_y;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_averages_patterns() {
+        let (white, black) = (white(), black());
+        let along_x = Stripe::new(white.clone(), black.clone());
+        let mut along_z = Stripe::new(white.clone(), black.clone());
+        along_z.set_transform(rotation_y(PI / 2.0)).unwrap();
+        let p = Blended::new(along_x, along_z);
+        let grey = Color::new(0.5, 0.5, 0.5);
+        assert_eq!(p.pattern_at(&Point::new(0.5, 0.0, -0.5)), white);
+        assert_eq!(p.pattern_at(&Point::new(0.5, 0.0, 0.5)), grey);
+        assert_eq!(p.pattern_at(&Point::new(1.5, 0.0, -0.5)), grey);
+        assert_eq!(p.pattern_at(&Point::new(1.5, 0.0, 0.5)), black);
+    }
+}
@@ -0,0 +1,62 @@
+use crate::color::Color;
+use crate::patterns::{is_even, two_pattern_struct, Pattern, PatternProperties};
+use crate::point::Point;
+
+two_pattern_struct! {
+    /// Unit cubes alternating in all three dimensions, with `a` at the origin.
+    Checker
+}
+
+impl Pattern for Checker {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        if is_even(point.x().floor() + point.y().floor() + point.z().floor()) {
+            self.a.color_at(point)
+        } else {
+            self.b.color_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{black, white};
+
+    #[test]
+    fn test_repeats_in_x() {
+        let p = Checker::new(white(), black());
+        assert_eq!(p.pattern_at(&Point::new(0.0, 0.0, 0.0)), white());
+        assert_eq!(p.pattern_at(&Point::new(0.99, 0.0, 0.0)), white());
+        assert_eq!(p.pattern_at(&Point::new(1.01, 0.0, 0.0)), black());
+    }
+
+    #[test]
+    fn test_repeats_in_y() {
+        let p = Checker::new(white(), black());
+        assert_eq!(p.pattern_at(&Point::new(0.0, 0.99, 0.0)), white());
+        assert_eq!(p.pattern_at(&Point::new(0.0, 1.01, 0.0)), black());
+    }
+
+    #[test]
+    fn test_repeats_in_z() {
+        let p = Checker::new(white(), black());
+        assert_eq!(p.pattern_at(&Point::new(0.0, 0.0, 0.99)), white());
+        assert_eq!(p.pattern_at(&Point::new(0.0, 0.0, 1.01)), black());
+    }
+
+    #[test]
+    fn test_negative_coordinates() {
+        let p = Checker::new(white(), black());
+        assert_eq!(p.pattern_at(&Point::new(-0.5, 0.0, 0.0)), black());
+        assert_eq!(p.pattern_at(&Point::new(-0.5, -0.5, 0.0)), white());
+        assert_eq!(p.pattern_at(&Point::new(-0.5, -0.5, -0.5)), black());
+    }
+}
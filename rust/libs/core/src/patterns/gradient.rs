@@ -0,0 +1,69 @@
+use crate::color::Color;
+use crate::patterns::{two_pattern_struct, Pattern, PatternProperties};
+use crate::point::Point;
+
+two_pattern_struct! {
+    /// Fades linearly from `a` at x = 0 to `b` at x = 1, then starts over.
+    Gradient
+}
+
+impl Pattern for Gradient {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        let a = self.a.color_at(point);
+        let b = self.b.color_at(point);
+        let fraction = point.x() - point.x().floor();
+        &a + &(&(&b - &a) * fraction)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::patterns::{black, white, Solid, Stripe};
+
+    #[test]
+    fn test_interpolates_between_colors() {
+        let p = Gradient::new(white(), black());
+        for (x, expected) in [
+            (0.0, 1.0),
+            (0.25, 0.75),
+            (0.5, 0.5),
+            (0.75, 0.25),
+            (1.0, 1.0),
+        ] {
+            assert_approx_eq!(
+                p.pattern_at(&Point::new(x, 0.0, 0.0)),
+                Color::new(expected, expected, expected)
+            );
+        }
+    }
+
+    #[test]
+    fn test_constant_in_y_and_z() {
+        let p = Gradient::new(white(), black());
+        assert_eq!(
+            p.pattern_at(&Point::new(0.25, 3.0, -7.0)),
+            p.pattern_at(&Point::new(0.25, 0.0, 0.0))
+        );
+    }
+
+    #[test]
+    fn test_nested() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let stripes = Stripe::new(red.clone(), blue.clone());
+        let p = Gradient::nested(stripes, Solid::new(black()));
+        assert_approx_eq!(p.pattern_at(&Point::new(0.0, 0.0, 0.0)), red);
+        assert_approx_eq!(p.pattern_at(&Point::new(0.5, 0.0, 0.0)), &red * 0.5);
+        assert_approx_eq!(p.pattern_at(&Point::new(1.5, 0.0, 0.0)), &blue * 0.5);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::color::Color;
+use crate::patterns::{Pattern, PatternProperties};
+use crate::point::Point;
+use crate::tuple::CoordValue;
+
+/// Another pattern sampled at points jittered by gradient noise, which makes
+/// its edges wobble. `scale` is how far, at most, a point is moved along each
+/// axis.
+#[derive(Debug)]
+pub struct Perturbed {
+    properties: PatternProperties,
+    pattern: Box<dyn Pattern>,
+    scale: CoordValue,
+}
+
+impl Perturbed {
+    pub fn new<P: Pattern + 'static>(pattern: P, scale: CoordValue) -> Perturbed {
+        Perturbed {
+            properties: PatternProperties::new(),
+            pattern: Box::new(pattern),
+            scale,
+        }
+    }
+
+    pub fn pattern(&self) -> &dyn Pattern {
+        self.pattern.as_ref()
+    }
+
+    pub fn scale(&self) -> CoordValue {
+        self.scale
+    }
+}
+
+impl Pattern for Perturbed {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        let (x, y, z) = (point.x(), point.y(), point.z());
+        // Offset samples so the three displacements are unrelated
+        let jittered = Point::new(
+            x + noise(x, y, z) * self.scale,
+            y + noise(x + 31.4, y + 15.9, z + 26.5) * self.scale,
+            z + noise(x + 35.8, y + 97.9, z + 32.3) * self.scale,
+        );
+        self.pattern.color_at(&jittered)
+    }
+}
+
+/// Gradient noise in [-1, 1], zero at every integer point.
+fn noise(x: CoordValue, y: CoordValue, z: CoordValue) -> CoordValue {
+    let (xi, yi, zi) = (x.floor(), y.floor(), z.floor());
+    let (xf, yf, zf) = (x - xi, y - yi, z - zi);
+    let (xi, yi, zi) = (xi as i64, yi as i64, zi as i64);
+    let (u, v, w) = (fade(xf), fade(yf), fade(zf));
+
+    let corner = |dx: i64, dy: i64, dz: i64| {
+        gradient(
+            hash(xi + dx, yi + dy, zi + dz),
+            xf - dx as CoordValue,
+            yf - dy as CoordValue,
+            zf - dz as CoordValue,
+        )
+    };
+
+    let x00 = lerp(u, corner(0, 0, 0), corner(1, 0, 0));
+    let x10 = lerp(u, corner(0, 1, 0), corner(1, 1, 0));
+    let x01 = lerp(u, corner(0, 0, 1), corner(1, 0, 1));
+    let x11 = lerp(u, corner(0, 1, 1), corner(1, 1, 1));
+    let value = lerp(w, lerp(v, x00, x10), lerp(v, x01, x11));
+    value.clamp(-1.0, 1.0)
+}
+
+/// Scrambles the coordinates of a lattice point into a few well-mixed bits.
+fn hash(x: i64, y: i64, z: i64) -> u64 {
+    let mut h = (x as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ (y as u64).wrapping_mul(0xc2b2_ae3d_27d4_eb4f)
+        ^ (z as u64).wrapping_mul(0x1656_67b1_9e37_79f9);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h
+}
+
+/// Dot product of the offset from a lattice point with one of the twelve
+/// cube edge directions, picked by `hash`.
+fn gradient(hash: u64, x: CoordValue, y: CoordValue, z: CoordValue) -> CoordValue {
+    match hash % 12 {
+        0 => x + y,
+        1 => -x + y,
+        2 => x - y,
+        3 => -x - y,
+        4 => x + z,
+        5 => -x + z,
+        6 => x - z,
+        7 => -x - z,
+        8 => y + z,
+        9 => -y + z,
+        10 => y - z,
+        _ => -y - z,
+    }
+}
+
+/// Smoothstep that also has a zero second derivative at 0 and 1.
+fn fade(t: CoordValue) -> CoordValue {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: CoordValue, a: CoordValue, b: CoordValue) -> CoordValue {
+    a + t * (b - a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{black, white, Stripe, TestPattern};
+
+    #[test]
+    fn test_zero_scale_is_inner_pattern() {
+        let p = Perturbed::new(TestPattern::new(), 0.0);
+        let point = Point::new(0.3, -1.7, 2.2);
+        assert_eq!(p.pattern_at(&point), Color::new(0.3, -1.7, 2.2));
+    }
+
+    #[test]
+    fn test_noise_is_zero_at_integer_points() {
+        for (x, y, z) in [(0.0, 0.0, 0.0), (1.0, -2.0, 3.0), (-5.0, 7.0, 11.0)] {
+            assert_eq!(noise(x, y, z), 0.0);
+        }
+    }
+
+    #[test]
+    fn test_noise_is_bounded_and_varies() {
+        let mut values = Vec::new();
+        for i in 0..200 {
+            let t = i as CoordValue * 0.137;
+            let value = noise(t, t * 0.5 + 0.3, 1.9 - t);
+            assert!((-1.0..=1.0).contains(&value));
+            values.push(value);
+        }
+        assert!(values.iter().any(|&v| v > 0.05));
+        assert!(values.iter().any(|&v| v < -0.05));
+    }
+
+    #[test]
+    fn test_displacement_is_limited_by_scale() {
+        let p = Perturbed::new(TestPattern::new(), 0.25);
+        for i in 0..50 {
+            let t = i as CoordValue * 0.31;
+            let point = Point::new(t, 1.0 - t, t * 0.7);
+            let c = p.pattern_at(&point);
+            assert!((c.red() - point.x()).abs() <= 0.25);
+            assert!((c.green() - point.y()).abs() <= 0.25);
+            assert!((c.blue() - point.z()).abs() <= 0.25);
+        }
+    }
+
+    #[test]
+    fn test_moves_boundaries() {
+        let plain = Stripe::new(white(), black());
+        let perturbed = Perturbed::new(Stripe::new(white(), black()), 0.5);
+        let differs = (0..100).any(|i| {
+            let point = Point::new(0.9 + i as CoordValue * 0.002, i as CoordValue * 0.173, 0.4);
+            plain.pattern_at(&point) != perturbed.pattern_at(&point)
+        });
+        assert!(differs);
+    }
+}
@@ -0,0 +1,46 @@
+use crate::color::Color;
+use crate::patterns::{is_even, two_pattern_struct, Pattern, PatternProperties};
+use crate::point::Point;
+
+two_pattern_struct! {
+    /// Concentric rings one unit wide around the y axis, starting with `a` at the
+    /// center.
+    Ring
+}
+
+impl Pattern for Ring {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        if is_even((point.x() * point.x() + point.z() * point.z()).sqrt()) {
+            self.a.color_at(point)
+        } else {
+            self.b.color_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{black, white};
+
+    #[test]
+    fn test_extends_in_x_and_z() {
+        let (white, black) = (white(), black());
+        let p = Ring::new(white.clone(), black.clone());
+        assert_eq!(p.pattern_at(&Point::new(0.0, 0.0, 0.0)), white);
+        assert_eq!(p.pattern_at(&Point::new(1.0, 0.0, 0.0)), black);
+        assert_eq!(p.pattern_at(&Point::new(0.0, 0.0, 1.0)), black);
+        // 0.708 is just slightly more than sqrt(2)/2
+        assert_eq!(p.pattern_at(&Point::new(0.708, 0.0, 0.708)), black);
+        assert_eq!(p.pattern_at(&Point::new(0.0, 5.0, 1.5)), black);
+        assert_eq!(p.pattern_at(&Point::new(0.0, 0.0, 2.5)), white);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::color::Color;
+use crate::patterns::{Pattern, PatternProperties};
+use crate::point::Point;
+
+/// A single color everywhere, mostly useful inside other patterns.
+#[derive(Debug, Clone)]
+pub struct Solid {
+    properties: PatternProperties,
+    color: Color,
+}
+
+impl Solid {
+    pub fn new(color: Color) -> Solid {
+        Solid {
+            properties: PatternProperties::new(),
+            color,
+        }
+    }
+
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+}
+
+impl Pattern for Solid {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn pattern_at(&self, _point: &Point) -> Color {
+        self.color.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_color_everywhere() {
+        let p = Solid::new(Color::new(0.2, 0.4, 0.6));
+        assert_eq!(*p.color(), Color::new(0.2, 0.4, 0.6));
+        for point in [Point::new(0.0, 0.0, 0.0), Point::new(-3.5, 12.0, 0.7)] {
+            assert_eq!(p.pattern_at(&point), Color::new(0.2, 0.4, 0.6));
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use crate::color::Color;
+use crate::patterns::{is_even, two_pattern_struct, Pattern, PatternProperties};
+use crate::point::Point;
+
+two_pattern_struct! {
+    /// Stripes one unit wide alternating along x, starting with `a` at x = 0.
+    Stripe
+}
+
+impl Pattern for Stripe {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        if is_even(point.x()) {
+            self.a.color_at(point)
+        } else {
+            self.b.color_at(point)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::patterns::{black, white, Solid};
+    use crate::shapes::{Shape, Sphere};
+    use crate::transform::{rotation_y, scaling, translation};
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_new() {
+        let p = Stripe::new(white(), black());
+        let origin = Point::new(0.0, 0.0, 0.0);
+        assert_eq!(p.a().pattern_at(&origin), white());
+        assert_eq!(p.b().pattern_at(&origin), black());
+    }
+
+    #[test]
+    fn test_constant_in_y_and_z() {
+        let p = Stripe::new(white(), black());
+        for (y, z) in [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (0.0, 1.0), (0.0, 2.0)] {
+            assert_eq!(p.pattern_at(&Point::new(0.0, y, z)), white());
+        }
+    }
+
+    #[test]
+    fn test_alternates_in_x() {
+        let p = Stripe::new(white(), black());
+        for (x, expected) in [
+            (0.0, white()),
+            (0.9, white()),
+            (1.0, black()),
+            (-0.1, black()),
+            (-1.0, black()),
+            (-1.1, white()),
+        ] {
+            assert_eq!(p.pattern_at(&Point::new(x, 0.0, 0.0)), expected);
+        }
+    }
+
+    #[test]
+    fn test_object_transform() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let p = Stripe::new(white(), black());
+        assert_eq!(p.color_at_object(&s, &Point::new(1.5, 0.0, 0.0)), white());
+    }
+
+    #[test]
+    fn test_pattern_transform() {
+        let mut p = Stripe::new(white(), black());
+        p.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        assert_eq!(
+            p.color_at_object(&Sphere::new(), &Point::new(1.5, 0.0, 0.0)),
+            white()
+        );
+    }
+
+    #[test]
+    fn test_object_and_pattern_transforms() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let mut p = Stripe::new(white(), black());
+        p.set_transform(translation(0.5, 0.0, 0.0)).unwrap();
+        assert_eq!(p.color_at_object(&s, &Point::new(2.5, 0.0, 0.0)), white());
+    }
+
+    #[test]
+    fn test_nested_patterns_have_their_own_transform() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        // Stripes along z inside the first stripe
+        let mut inner = Stripe::new(red.clone(), white());
+        inner.set_transform(rotation_y(PI / 2.0)).unwrap();
+        let p = Stripe::nested(inner, Solid::new(black()));
+        assert_eq!(p.pattern_at(&Point::new(0.5, 0.0, -0.5)), red);
+        assert_eq!(p.pattern_at(&Point::new(0.5, 0.0, 0.5)), white());
+        assert_eq!(p.pattern_at(&Point::new(1.5, 0.0, 0.5)), black());
+    }
+}
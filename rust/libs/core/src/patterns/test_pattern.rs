@@ -0,0 +1,101 @@
+use crate::color::Color;
+use crate::patterns::{Pattern, PatternProperties};
+use crate::point::Point;
+
+/// A pattern meant for testing the object/pattern space plumbing of
+/// [`Pattern`]: its color at a point is that point's coordinates.
+#[derive(Debug, Clone, Default)]
+pub struct TestPattern {
+    properties: PatternProperties,
+}
+
+impl TestPattern {
+    pub fn new() -> TestPattern {
+        TestPattern::default()
+    }
+}
+
+impl Pattern for TestPattern {
+    fn properties(&self) -> &PatternProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut PatternProperties {
+        &mut self.properties
+    }
+
+    fn pattern_at(&self, point: &Point) -> Color {
+        Color::new(point.x(), point.y(), point.z())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixed_matrix::Matrix4;
+    use crate::matrix::{Matrix, MatrixError};
+    use crate::shapes::{Group, Shape, Sphere};
+    use crate::transform::{scaling, translation};
+
+    #[test]
+    fn test_default_transform() {
+        assert_eq!(*TestPattern::new().transform(), Matrix4::identity());
+    }
+
+    #[test]
+    fn test_set_transform() {
+        let mut p = TestPattern::new();
+        p.set_transform(translation(1.0, 2.0, 3.0)).unwrap();
+        assert_eq!(
+            *p.transform(),
+            Matrix4::try_from(translation(1.0, 2.0, 3.0)).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_set_transform_rejects_singular_matrix() {
+        let mut p = TestPattern::new();
+        assert_eq!(
+            p.set_transform(Matrix::new(4, 4)),
+            Err(MatrixError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn test_object_transform() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let c = TestPattern::new().color_at_object(&s, &Point::new(2.0, 3.0, 4.0));
+        assert_eq!(c, Color::new(1.0, 1.5, 2.0));
+    }
+
+    #[test]
+    fn test_pattern_transform() {
+        let mut p = TestPattern::new();
+        p.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let c = p.color_at_object(&Sphere::new(), &Point::new(2.0, 3.0, 4.0));
+        assert_eq!(c, Color::new(1.0, 1.5, 2.0));
+    }
+
+    #[test]
+    fn test_object_and_pattern_transforms() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let mut p = TestPattern::new();
+        p.set_transform(translation(0.5, 1.0, 1.5)).unwrap();
+        let c = p.color_at_object(&s, &Point::new(2.5, 3.0, 3.5));
+        assert_eq!(c, Color::new(0.75, 0.5, 0.25));
+    }
+
+    #[test]
+    fn test_object_in_group() {
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let mut g = Group::new();
+        g.set_transform(translation(10.0, 0.0, 0.0)).unwrap();
+        g.add_child(s);
+        let s = g.children()[0].as_ref();
+        let c = TestPattern::new().color_at_object(s, &Point::new(12.0, 3.0, 4.0));
+        assert_eq!(c, Color::new(1.0, 1.5, 2.0));
+    }
+}
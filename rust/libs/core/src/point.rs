@@ -1,7 +1,36 @@
-use crate::tuple::{CoordValue, Tuple};
+use crate::tuple::{forward_binop, CoordValue, Tuple};
 use crate::vector::Vector;
+use std::{fmt, ops};
 
+/// Returned when converting a tuple whose `w` is 0, i.e. a vector, to a point.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NotAPointError;
+
+impl fmt::Display for NotAPointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "tuple with w = 0 is a vector, not a point")
+    }
+}
+
+impl std::error::Error for NotAPointError {}
+
+/// A position in space.
+///
+/// Points can be moved by vectors and subtracted from each other to get the
+/// vector between them, but adding two points is meaningless and does not
+/// compile:
+///
+/// ```compile_fail
+/// use core::point::Point;
+///
+/// let p = Point::new(1.0, 2.0, 3.0) + Point::new(4.0, 5.0, 6.0);
+/// ```
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "crate::tuple::Coordinates", into = "crate::tuple::Coordinates")
+)]
 pub struct Point {
     tuple: Tuple,
 }
@@ -9,24 +38,37 @@ pub struct Point {
 impl Point {
     pub fn new(x: CoordValue, y: CoordValue, z: CoordValue) -> Point {
         Point {
-            tuple: Tuple::new(x, y, z),
+            tuple: Tuple::point(x, y, z),
         }
     }
 
+    /// Builds a point from a tuple, treating a `w` of 0 as 1 and dividing by any
+    /// other `w` (e.g. after a projective transformation). Use `try_from` to
+    /// reject vectors instead.
     pub fn from(t: Tuple) -> Point {
-        Point { tuple: t }
+        if t.w == 0.0 {
+            Point::new(t.x, t.y, t.z)
+        } else {
+            Point::project(t)
+        }
     }
 
-    pub fn from_array(a: [CoordValue; 3]) -> Point {
-        Point {
-            tuple: Tuple::from_array(a),
+    /// Projects a homogeneous tuple, e.g. the product of a matrix and a point,
+    /// back to a point. A `w` of 0 gives a point at infinity.
+    pub(crate) fn project(t: Tuple) -> Point {
+        if t.w == 1.0 {
+            Point::new(t.x, t.y, t.z)
+        } else {
+            Point::new(t.x / t.w, t.y / t.w, t.z / t.w)
         }
     }
 
+    pub fn from_array(a: [CoordValue; 3]) -> Point {
+        Point::new(a[0], a[1], a[2])
+    }
+
     pub fn from_tuple(t: (CoordValue, CoordValue, CoordValue)) -> Point {
-        Point {
-            tuple: Tuple::from_tuple(t),
-        }
+        Point::new(t.0, t.1, t.2)
     }
 
     pub fn from_point(p: &Point) -> Point {
@@ -72,12 +114,90 @@ impl Point {
     }
 }
 
+/// Divides by `w` when it is not 1 (e.g. after a projective transformation).
+impl TryFrom<Tuple> for Point {
+    type Error = NotAPointError;
+
+    fn try_from(t: Tuple) -> Result<Point, NotAPointError> {
+        if t.w == 0.0 {
+            Err(NotAPointError)
+        } else {
+            Ok(Point::project(t))
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<crate::tuple::Coordinates> for Point {
+    fn from(c: crate::tuple::Coordinates) -> Point {
+        Point::from_array(c.as_array())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Point> for crate::tuple::Coordinates {
+    fn from(v: Point) -> crate::tuple::Coordinates {
+        crate::tuple::Coordinates::new(v.tuple.x, v.tuple.y, v.tuple.z)
+    }
+}
+
 impl PartialEq for Point {
     fn eq(&self, other: &Point) -> bool {
         self.tuple.eq(&other.tuple)
     }
 }
 
+impl ops::Add<&Vector> for &Point {
+    type Output = Point;
+
+    fn add(self, other: &Vector) -> Point {
+        self.add_vector(other)
+    }
+}
+
+impl ops::Sub<&Vector> for &Point {
+    type Output = Point;
+
+    fn sub(self, other: &Vector) -> Point {
+        self.sub_vector(other)
+    }
+}
+
+impl ops::Sub<&Point> for &Point {
+    type Output = Vector;
+
+    fn sub(self, other: &Point) -> Vector {
+        Vector::from(Tuple::sub(&self.tuple, &other.tuple))
+    }
+}
+
+forward_binop!(Point, Add, add, AddAssign, add_assign, Vector, Point);
+forward_binop!(Point, Sub, sub, SubAssign, sub_assign, Vector, Point);
+
+impl ops::Sub<Point> for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        &self - &other
+    }
+}
+
+impl ops::Sub<&Point> for Point {
+    type Output = Vector;
+
+    fn sub(self, other: &Point) -> Vector {
+        &self - other
+    }
+}
+
+impl ops::Sub<Point> for &Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Vector {
+        self - &other
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -99,6 +219,15 @@ mod tests {
         assert_eq!(p.tuple.z, 3.0);
     }
 
+    #[test]
+    fn test_try_from() {
+        let t = Tuple::point(1.0, 2.0, 3.0);
+        let p = Point::try_from(t).unwrap();
+        assert_eq!(p.tuple.x, 1.0);
+        assert_eq!(p.tuple.y, 2.0);
+        assert_eq!(p.tuple.z, 3.0);
+    }
+
     #[test]
     fn test_from_array() {
         let p = Point::from_array([1.0, 2.0, 3.0]);
@@ -177,4 +306,101 @@ mod tests {
         assert_eq!(p2.tuple.y, 0.0);
         assert_eq!(p2.tuple.z, 0.0);
     }
+
+    #[test]
+    fn test_w_is_1() {
+        let p = Point::new(1.0, 2.0, 3.0);
+        assert_eq!(p.tuple.w, 1.0);
+        assert!(p.as_tuple().is_point());
+        assert_eq!(Point::from_array([1.0, 2.0, 3.0]).tuple.w, 1.0);
+        assert_eq!(Point::from_tuple((1.0, 2.0, 3.0)).tuple.w, 1.0);
+    }
+
+    #[test]
+    fn test_round_trip_through_tuple() {
+        let p = Point::new(1.0, -2.0, 3.0);
+        assert_eq!(Point::try_from(p.as_tuple()), Ok(p));
+    }
+
+    #[test]
+    fn test_from_applies_perspective_divide() {
+        let t = Tuple::homogeneous(2.0, 4.0, -6.0, 2.0);
+        assert_eq!(Point::from(t), Point::new(1.0, 2.0, -3.0));
+    }
+
+    #[test]
+    fn test_try_from_applies_perspective_divide() {
+        let t = Tuple::homogeneous(2.0, 4.0, -6.0, 2.0);
+        assert_eq!(Point::try_from(t), Ok(Point::new(1.0, 2.0, -3.0)));
+    }
+
+    #[test]
+    fn test_try_from_rejects_vectors() {
+        let t = Tuple::vector(1.0, 2.0, 3.0);
+        assert_eq!(Point::try_from(t), Err(NotAPointError));
+        assert_eq!(
+            NotAPointError.to_string(),
+            "tuple with w = 0 is a vector, not a point"
+        );
+    }
+
+    #[test]
+    fn test_project_point_at_infinity() {
+        let p = Point::project(Tuple::vector(1.0, 0.0, -1.0));
+        assert_eq!(p.x(), CoordValue::INFINITY);
+        assert_eq!(p.z(), CoordValue::NEG_INFINITY);
+    }
+
+    #[test]
+    fn test_sub_vector_keeps_w() {
+        let p = Point::new(1.0, 2.0, 3.0);
+        let v = Vector::new(1.0, 2.0, 3.0);
+        assert_eq!(p.sub_vector(&v).tuple.w, 1.0);
+    }
+
+    #[test]
+    fn test_add_vector_operator() {
+        let p = Point::new(3.0, -2.0, 5.0);
+        let v = Vector::new(-2.0, 3.0, 1.0);
+        assert_eq!(&p + &v, Point::new(1.0, 1.0, 6.0));
+        assert_eq!(p + v, Point::new(1.0, 1.0, 6.0));
+    }
+
+    #[test]
+    fn test_sub_vector_operator() {
+        let p = Point::new(3.0, 2.0, 1.0);
+        let v = Vector::new(5.0, 6.0, 7.0);
+        assert_eq!(&p - &v, Point::new(-2.0, -4.0, -6.0));
+        assert_eq!(p - v, Point::new(-2.0, -4.0, -6.0));
+    }
+
+    #[test]
+    fn test_sub_points_operator() {
+        let p1 = Point::new(3.0, 2.0, 1.0);
+        let p2 = Point::new(5.0, 6.0, 7.0);
+        assert_eq!(&p1 - &p2, Vector::new(-2.0, -4.0, -6.0));
+        assert_eq!(p1.clone() - &p2, Vector::new(-2.0, -4.0, -6.0));
+        assert_eq!(&p1 - p2.clone(), Vector::new(-2.0, -4.0, -6.0));
+        assert_eq!(p1 - p2, Vector::new(-2.0, -4.0, -6.0));
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let mut p = Point::new(1.0, 2.0, 3.0);
+        p += Vector::new(1.0, 1.0, 1.0);
+        assert_eq!(p, Point::new(2.0, 3.0, 4.0));
+        p -= &Vector::new(2.0, 2.0, 2.0);
+        assert_eq!(p, Point::new(0.0, 1.0, 2.0));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let p = Point::new(1.0, -2.5, 3.0);
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(json, r#"{"x":1.0,"y":-2.5,"z":3.0}"#);
+        let back: Point = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, p);
+        assert!(back.as_tuple().is_point());
+    }
 }
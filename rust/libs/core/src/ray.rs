@@ -0,0 +1,363 @@
+use crate::fixed_matrix::Matrix4;
+use crate::matrix::{Matrix, MatrixError};
+use crate::point::Point;
+use crate::tuple::CoordValue;
+use crate::vector::Vector;
+use std::ops;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ray {
+    pub origin: Point,
+    pub direction: Vector,
+}
+
+impl Ray {
+    pub fn new(origin: Point, direction: Vector) -> Ray {
+        Ray { origin, direction }
+    }
+
+    /// The point reached after travelling `t` times the direction from the origin.
+    pub fn position(&self, t: CoordValue) -> Point {
+        &self.origin + &(&self.direction * t)
+    }
+
+    /// Applies `m` to both the origin and the direction, so translations only
+    /// move the origin. `m` must be a 3x3 or 4x4 matrix.
+    pub fn transform(&self, m: &Matrix) -> Result<Ray, MatrixError> {
+        Ok(Ray {
+            origin: (m * &self.origin)?,
+            direction: (m * &self.direction)?,
+        })
+    }
+}
+
+impl ops::Mul<&Ray> for &Matrix4 {
+    type Output = Ray;
+
+    fn mul(self, ray: &Ray) -> Ray {
+        Ray {
+            origin: self * &ray.origin,
+            direction: self * &ray.direction,
+        }
+    }
+}
+
+impl ops::Mul<Ray> for Matrix4 {
+    type Output = Ray;
+
+    fn mul(self, ray: Ray) -> Ray {
+        &self * &ray
+    }
+}
+
+/// The distance `t` along a ray at which it meets `object`.
+#[derive(Debug)]
+pub struct Intersection<'a, O: ?Sized> {
+    pub t: CoordValue,
+    pub object: &'a O,
+    /// Barycentric coordinates of the hit, for shapes such as triangles that
+    /// interpolate values across their surface.
+    pub uv: Option<(CoordValue, CoordValue)>,
+}
+
+impl<'a, O: ?Sized> Intersection<'a, O> {
+    pub fn new(t: CoordValue, object: &'a O) -> Intersection<'a, O> {
+        Intersection {
+            t,
+            object,
+            uv: None,
+        }
+    }
+
+    pub fn with_uv(
+        t: CoordValue,
+        object: &'a O,
+        u: CoordValue,
+        v: CoordValue,
+    ) -> Intersection<'a, O> {
+        Intersection {
+            t,
+            object,
+            uv: Some((u, v)),
+        }
+    }
+}
+
+impl<O: ?Sized> Clone for Intersection<'_, O> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<O: ?Sized> Copy for Intersection<'_, O> {}
+
+/// Intersections are equal when they are at the same `t` on the very same object.
+impl<O: ?Sized> PartialEq for Intersection<'_, O> {
+    fn eq(&self, other: &Self) -> bool {
+        self.t == other.t && std::ptr::addr_eq(self.object, other.object)
+    }
+}
+
+/// A collection of intersections, kept sorted by increasing `t`.
+#[derive(Debug)]
+pub struct Intersections<'a, O: ?Sized> {
+    items: Vec<Intersection<'a, O>>,
+}
+
+impl<'a, O: ?Sized> Intersections<'a, O> {
+    pub fn new() -> Intersections<'a, O> {
+        Intersections { items: Vec::new() }
+    }
+
+    pub fn from_vec(mut items: Vec<Intersection<'a, O>>) -> Intersections<'a, O> {
+        items.sort_by(|a, b| a.t.total_cmp(&b.t));
+        Intersections { items }
+    }
+
+    pub fn push(&mut self, intersection: Intersection<'a, O>) {
+        let index = self
+            .items
+            .partition_point(|i| i.t.total_cmp(&intersection.t).is_le());
+        self.items.insert(index, intersection);
+    }
+
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    pub fn get(&self, index: usize) -> Option<&Intersection<'a, O>> {
+        self.items.get(index)
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Intersection<'a, O>> {
+        self.items.iter()
+    }
+
+    pub fn as_slice(&self) -> &[Intersection<'a, O>] {
+        &self.items
+    }
+
+    /// The visible intersection, i.e. the one with the lowest non-negative `t`.
+    /// Intersections behind the ray origin are never hits.
+    pub fn hit(&self) -> Option<&Intersection<'a, O>> {
+        self.items.iter().find(|i| i.t >= 0.0)
+    }
+}
+
+impl<O: ?Sized> Default for Intersections<'_, O> {
+    fn default() -> Self {
+        Intersections::new()
+    }
+}
+
+impl<'a, O: ?Sized> ops::Index<usize> for Intersections<'a, O> {
+    type Output = Intersection<'a, O>;
+
+    fn index(&self, index: usize) -> &Intersection<'a, O> {
+        &self.items[index]
+    }
+}
+
+impl<'a, O: ?Sized> FromIterator<Intersection<'a, O>> for Intersections<'a, O> {
+    fn from_iter<I: IntoIterator<Item = Intersection<'a, O>>>(iter: I) -> Self {
+        Intersections::from_vec(iter.into_iter().collect())
+    }
+}
+
+impl<'a, O: ?Sized> Extend<Intersection<'a, O>> for Intersections<'a, O> {
+    fn extend<I: IntoIterator<Item = Intersection<'a, O>>>(&mut self, iter: I) {
+        self.items.extend(iter);
+        self.items.sort_by(|a, b| a.t.total_cmp(&b.t));
+    }
+}
+
+impl<'a, O: ?Sized> IntoIterator for Intersections<'a, O> {
+    type Item = Intersection<'a, O>;
+    type IntoIter = std::vec::IntoIter<Intersection<'a, O>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.into_iter()
+    }
+}
+
+impl<'a, 'b, O: ?Sized> IntoIterator for &'b Intersections<'a, O> {
+    type Item = &'b Intersection<'a, O>;
+    type IntoIter = std::slice::Iter<'b, Intersection<'a, O>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transform::{scaling, translation};
+
+    #[test]
+    fn test_new() {
+        let origin = Point::new(1.0, 2.0, 3.0);
+        let direction = Vector::new(4.0, 5.0, 6.0);
+        let r = Ray::new(origin.clone(), direction.clone());
+        assert_eq!(r.origin, origin);
+        assert_eq!(r.direction, direction);
+    }
+
+    #[test]
+    fn test_position() {
+        let r = Ray::new(Point::new(2.0, 3.0, 4.0), Vector::new(1.0, 0.0, 0.0));
+        assert_eq!(r.position(0.0), Point::new(2.0, 3.0, 4.0));
+        assert_eq!(r.position(1.0), Point::new(3.0, 3.0, 4.0));
+        assert_eq!(r.position(-1.0), Point::new(1.0, 3.0, 4.0));
+        assert_eq!(r.position(2.5), Point::new(4.5, 3.0, 4.0));
+    }
+
+    #[test]
+    fn test_translate() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0));
+        let r2 = r.transform(&translation(3.0, 4.0, 5.0)).unwrap();
+        assert_eq!(r2.origin, Point::new(4.0, 6.0, 8.0));
+        assert_eq!(r2.direction, Vector::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_scale() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0));
+        let r2 = r.transform(&scaling(2.0, 3.0, 4.0)).unwrap();
+        assert_eq!(r2.origin, Point::new(2.0, 6.0, 12.0));
+        assert_eq!(r2.direction, Vector::new(0.0, 3.0, 0.0));
+    }
+
+    #[test]
+    fn test_transform_leaves_original_untouched() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0));
+        let _ = r.transform(&scaling(2.0, 3.0, 4.0)).unwrap();
+        assert_eq!(r.origin, Point::new(1.0, 2.0, 3.0));
+    }
+
+    #[test]
+    fn test_transform_rejects_wrong_size() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0));
+        assert!(matches!(
+            r.transform(&Matrix::identity(2)),
+            Err(MatrixError::DimensionMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_transform_by_fixed_matrix() {
+        let r = Ray::new(Point::new(1.0, 2.0, 3.0), Vector::new(0.0, 1.0, 0.0));
+        let m = Matrix4::try_from(translation(3.0, 4.0, 5.0)).unwrap();
+        assert_eq!(&m * &r, r.transform(&translation(3.0, 4.0, 5.0)).unwrap());
+    }
+
+    #[test]
+    fn test_intersection() {
+        let object = "sphere";
+        let i = Intersection::new(3.5, &object);
+        assert_eq!(i.t, 3.5);
+        assert!(std::ptr::eq(i.object, &object));
+        assert_eq!(i.uv, None);
+    }
+
+    #[test]
+    fn test_intersection_with_uv() {
+        let object = "triangle";
+        let i = Intersection::with_uv(3.5, &object, 0.2, 0.4);
+        assert_eq!(i.t, 3.5);
+        assert_eq!(i.uv, Some((0.2, 0.4)));
+    }
+
+    #[test]
+    fn test_intersections_are_sorted() {
+        let object = "sphere";
+        let mut xs = Intersections::from_vec(vec![
+            Intersection::new(5.0, &object),
+            Intersection::new(-3.0, &object),
+            Intersection::new(2.0, &object),
+        ]);
+        xs.push(Intersection::new(1.0, &object));
+        xs.extend([Intersection::new(7.0, &object)]);
+        let ts: Vec<CoordValue> = xs.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![-3.0, 1.0, 2.0, 5.0, 7.0]);
+        assert_eq!(xs.len(), 5);
+        assert_eq!(xs[1].t, 1.0);
+    }
+
+    #[test]
+    fn test_push_orders_nan_like_from_vec() {
+        let object = "sphere";
+        let ts = [
+            CoordValue::NAN,
+            2.0,
+            -CoordValue::NAN,
+            1.0,
+            CoordValue::NAN,
+            0.5,
+        ];
+        let mut pushed = Intersections::from_vec(vec![]);
+        for t in ts {
+            pushed.push(Intersection::new(t, &object));
+        }
+        let sorted = Intersections::from_vec(ts.map(|t| Intersection::new(t, &object)).to_vec());
+        let bits = |xs: &Intersections<&str>| xs.iter().map(|i| i.t.to_bits()).collect::<Vec<_>>();
+        assert_eq!(bits(&pushed), bits(&sorted));
+        assert_eq!(pushed[1].t, 0.5);
+    }
+
+    #[test]
+    fn test_hit_when_all_intersections_are_positive() {
+        let object = "sphere";
+        let i1 = Intersection::new(1.0, &object);
+        let i2 = Intersection::new(2.0, &object);
+        let xs = Intersections::from_vec(vec![i2, i1]);
+        assert_eq!(xs.hit(), Some(&i1));
+    }
+
+    #[test]
+    fn test_hit_when_some_intersections_are_negative() {
+        let object = "sphere";
+        let i1 = Intersection::new(-1.0, &object);
+        let i2 = Intersection::new(1.0, &object);
+        let xs = Intersections::from_vec(vec![i2, i1]);
+        assert_eq!(xs.hit(), Some(&i2));
+    }
+
+    #[test]
+    fn test_no_hit_when_all_intersections_are_negative() {
+        let object = "sphere";
+        let xs: Intersections<&str> = [-2.0, -1.0]
+            .into_iter()
+            .map(|t| Intersection::new(t, &object))
+            .collect();
+        assert_eq!(xs.hit(), None);
+        assert_eq!(Intersections::<&str>::new().hit(), None);
+    }
+
+    #[test]
+    fn test_hit_is_lowest_non_negative_intersection() {
+        let object = "sphere";
+        let i4 = Intersection::new(2.0, &object);
+        let xs = Intersections::from_vec(vec![
+            Intersection::new(5.0, &object),
+            Intersection::new(7.0, &object),
+            Intersection::new(-3.0, &object),
+            i4,
+        ]);
+        assert_eq!(xs.hit(), Some(&i4));
+    }
+
+    #[test]
+    fn test_hit_distinguishes_objects() {
+        let a = "a";
+        let b = "b";
+        let xs =
+            Intersections::from_vec(vec![Intersection::new(1.0, &a), Intersection::new(1.0, &b)]);
+        assert!(std::ptr::eq(xs.hit().unwrap().object, &a));
+        assert_ne!(xs[0], xs[1]);
+    }
+}
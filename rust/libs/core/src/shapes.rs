@@ -0,0 +1,185 @@
+mod cone;
+mod csg;
+mod cube;
+mod cylinder;
+mod group;
+mod plane;
+mod smooth_triangle;
+mod sphere;
+mod test_shape;
+mod triangle;
+
+pub use cone::Cone;
+pub use csg::{Csg, CsgOperation};
+pub use cube::Cube;
+pub use cylinder::Cylinder;
+pub use group::Group;
+pub use plane::Plane;
+pub use smooth_triangle::SmoothTriangle;
+pub use sphere::Sphere;
+#[doc(hidden)]
+pub use test_shape::TestShape;
+pub use triangle::Triangle;
+
+use crate::bounds::Bounds;
+use crate::bvh::BvhConfig;
+use crate::fixed_matrix::Matrix4;
+use crate::material::Material;
+use crate::matrix::{Matrix, MatrixError};
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use std::any::Any;
+use std::fmt;
+
+/// State shared by every shape: its transform (kept together with its inverse,
+/// which is what intersections and normals actually need) and its material.
+///
+/// The transform is relative to the group the shape belongs to, if any. The
+/// inverses of the enclosing groups' transforms are kept combined, so that
+/// world space points and normals go through the whole hierarchy at once.
+#[derive(Debug, Clone)]
+pub struct ShapeProperties {
+    transform: Matrix4,
+    inverse_transform: Matrix4,
+    parent_inverse: Matrix4,
+    world_inverse: Matrix4,
+    pub material: Material,
+}
+
+impl ShapeProperties {
+    pub fn new() -> ShapeProperties {
+        ShapeProperties {
+            transform: Matrix4::identity(),
+            inverse_transform: Matrix4::identity(),
+            parent_inverse: Matrix4::identity(),
+            world_inverse: Matrix4::identity(),
+            material: Material::default(),
+        }
+    }
+
+    fn set_parent_inverse(&mut self, parent_inverse: Matrix4) {
+        self.parent_inverse = parent_inverse;
+        self.world_inverse = self.inverse_transform * parent_inverse;
+    }
+}
+
+impl Default for ShapeProperties {
+    fn default() -> Self {
+        ShapeProperties::new()
+    }
+}
+
+/// A renderable object. Implementors work in object space, where they are
+/// centered at the origin with unit size, and only provide
+/// [`Shape::local_intersect`] and [`Shape::local_normal_at`]; the provided
+/// methods take care of moving rays and normals between world and object space.
+///
+/// Shapes can be downcast to their concrete type through [`Any`], e.g. to
+/// inspect the groups among the children of a group. They are `Send + Sync`
+/// so that a world can be rendered from several threads.
+pub trait Shape: Any + fmt::Debug + Send + Sync {
+    fn properties(&self) -> &ShapeProperties;
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties;
+
+    /// Intersections of an object-space ray with this shape.
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape>;
+
+    /// Normal at an object-space point on the surface of this shape.
+    fn local_normal_at(&self, point: &Point) -> Normal;
+
+    /// Box containing this shape, in object space.
+    fn local_bounds(&self) -> Bounds;
+
+    /// Normal at an object-space point, knowing the intersection that found
+    /// it. Only shapes that interpolate their normals, like smooth triangles,
+    /// need the hit; the others ignore it.
+    fn local_normal_at_hit(&self, point: &Point, _hit: &Intersection<'_, dyn Shape>) -> Normal {
+        self.local_normal_at(point)
+    }
+
+    fn transform(&self) -> &Matrix4 {
+        &self.properties().transform
+    }
+
+    fn inverse_transform(&self) -> &Matrix4 {
+        &self.properties().inverse_transform
+    }
+
+    /// Sets the object-to-parent transformation, which must be an invertible
+    /// 4x4 matrix. The parent is the world unless the shape is in a group.
+    fn set_transform(&mut self, transform: Matrix) -> Result<(), MatrixError> {
+        let transform = Matrix4::try_from(transform)?;
+        let inverse_transform = transform.inverse()?;
+        let properties = self.properties_mut();
+        properties.transform = transform;
+        properties.inverse_transform = inverse_transform;
+        let parent_inverse = properties.parent_inverse;
+        self.set_parent_inverse(parent_inverse);
+        Ok(())
+    }
+
+    /// Places this shape in a group whose world-to-group transformation is
+    /// `parent_inverse`. Called by groups on their children; groups pass the
+    /// change on to their own children.
+    fn set_parent_inverse(&mut self, parent_inverse: Matrix4) {
+        self.properties_mut().set_parent_inverse(parent_inverse);
+    }
+
+    /// Box containing this shape, in the space of its parent group (or of the
+    /// world).
+    fn parent_space_bounds(&self) -> Bounds {
+        self.local_bounds().transform(self.transform())
+    }
+
+    /// Organizes the shapes this shape is made of, if any, into a bounding
+    /// volume hierarchy. Only worth it for shapes made of many others, such as
+    /// groups loaded from OBJ files.
+    fn build_bvh(&mut self, _config: &BvhConfig) {}
+
+    /// Whether `other` is this very shape or, for shapes made of other shapes,
+    /// one of its descendants.
+    fn includes(&self, other: &dyn Shape) -> bool {
+        std::ptr::addr_eq(self as *const Self, other as *const dyn Shape)
+    }
+
+    /// Transformation from world space to object space, through every
+    /// enclosing group.
+    fn world_inverse(&self) -> &Matrix4 {
+        &self.properties().world_inverse
+    }
+
+    fn world_to_object(&self, point: &Point) -> Point {
+        self.world_inverse() * point
+    }
+
+    /// Converts an object-space normal to world space, through every
+    /// enclosing group.
+    fn normal_to_world(&self, normal: &Normal) -> Normal {
+        normal.transform_by_fixed_inverse(self.world_inverse())
+    }
+
+    fn material(&self) -> &Material {
+        &self.properties().material
+    }
+
+    fn set_material(&mut self, material: Material) {
+        self.properties_mut().material = material;
+    }
+
+    /// Intersections of a world-space ray with this shape.
+    fn intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        self.local_intersect(&(self.inverse_transform() * ray))
+    }
+
+    /// Normal at a world-space point on the surface of this shape.
+    fn normal_at(&self, point: &Point) -> Normal {
+        self.normal_to_world(&self.local_normal_at(&self.world_to_object(point)))
+    }
+
+    /// Normal at a world-space point, for the intersection `hit` that found it.
+    fn normal_at_hit(&self, point: &Point, hit: &Intersection<'_, dyn Shape>) -> Normal {
+        self.normal_to_world(&self.local_normal_at_hit(&self.world_to_object(point), hit))
+    }
+}
@@ -0,0 +1,232 @@
+use crate::approx::EPSILON;
+use crate::bounds::Bounds;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use crate::shapes::cylinder::within_radius;
+use crate::shapes::{Shape, ShapeProperties};
+use crate::tuple::CoordValue;
+
+/// A double-napped cone around the y axis with its apex at the origin, whose
+/// radius at height y is |y|. Like [`Cylinder`](crate::shapes::Cylinder) it can
+/// be truncated to `minimum..maximum` and capped.
+#[derive(Debug, Clone)]
+pub struct Cone {
+    properties: ShapeProperties,
+    pub minimum: CoordValue,
+    pub maximum: CoordValue,
+    pub closed: bool,
+}
+
+impl Cone {
+    /// An infinite double cone.
+    pub fn new() -> Cone {
+        Cone::truncated(CoordValue::NEG_INFINITY, CoordValue::INFINITY, false)
+    }
+
+    pub fn truncated(minimum: CoordValue, maximum: CoordValue, closed: bool) -> Cone {
+        Cone {
+            properties: ShapeProperties::new(),
+            minimum,
+            maximum,
+            closed,
+        }
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a, dyn Shape>) {
+        if !self.closed || ray.direction.y().abs() <= EPSILON * ray.direction.magnitude() {
+            return;
+        }
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.y()) / ray.direction.y();
+            if within_radius(ray, t, y.abs()) {
+                xs.push(Intersection::new(t, self as &dyn Shape));
+            }
+        }
+    }
+
+    fn push_if_in_bounds<'a>(
+        &'a self,
+        ray: &Ray,
+        t: CoordValue,
+        xs: &mut Intersections<'a, dyn Shape>,
+    ) {
+        let y = ray.origin.y() + t * ray.direction.y();
+        if self.minimum < y && y < self.maximum {
+            xs.push(Intersection::new(t, self as &dyn Shape));
+        }
+    }
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Cone::new()
+    }
+}
+
+impl Shape for Cone {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        let mut xs = Intersections::new();
+        let (ox, oy, oz) = ray.origin.as_coordinates();
+        let (dx, dy, dz) = ray.direction.as_features();
+        let a = dx * dx - dy * dy + dz * dz;
+        let b = 2.0 * (ox * dx - oy * dy + oz * dz);
+        let c = ox * ox - oy * oy + oz * oz;
+        // Tolerances are relative to the direction's length, which scaling the
+        // shape shrinks
+        let length = ray.direction.magnitude();
+        if a.abs() <= EPSILON * length * length {
+            // Parallel to one of the halves: a single intersection with the
+            // other one, if any
+            if b.abs() > EPSILON * length {
+                self.push_if_in_bounds(ray, -c / (2.0 * b), &mut xs);
+            }
+        } else {
+            let discriminant = b * b - 4.0 * a * c;
+            // Tangent rays may come out slightly negative after rounding
+            if discriminant < -EPSILON * length * length {
+                return xs;
+            }
+            let sqrt_discriminant = CoordValue::sqrt(discriminant.max(0.0));
+            self.push_if_in_bounds(ray, (-b - sqrt_discriminant) / (2.0 * a), &mut xs);
+            self.push_if_in_bounds(ray, (-b + sqrt_discriminant) / (2.0 * a), &mut xs);
+        }
+        self.intersect_caps(ray, &mut xs);
+        xs
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Normal {
+        let (x, y, z) = point.as_coordinates();
+        let distance = x * x + z * z;
+        if distance < y * y && y >= self.maximum - EPSILON {
+            Normal::new(0.0, 1.0, 0.0)
+        } else if distance < y * y && y <= self.minimum + EPSILON {
+            Normal::new(0.0, -1.0, 0.0)
+        } else {
+            let radius = CoordValue::sqrt(distance);
+            let ny = if y > 0.0 { -radius } else { radius };
+            Normal::new(x, ny, z)
+        }
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        // The radius at any height is the distance to the apex
+        let radius = self.minimum.abs().max(self.maximum.abs());
+        Bounds::new(
+            Point::new(-radius, self.minimum, -radius),
+            Point::new(radius, self.maximum, radius),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::scaling;
+    use crate::vector::Vector;
+
+    fn ray(
+        origin: (CoordValue, CoordValue, CoordValue),
+        direction: (CoordValue, CoordValue, CoordValue),
+    ) -> Ray {
+        Ray::new(
+            Point::from_tuple(origin),
+            Vector::from_tuple(direction).normalize(),
+        )
+    }
+
+    #[test]
+    fn test_ray_hits() {
+        let c = Cone::new();
+        for (origin, direction, t0, t1) in [
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0), 8.66025, 8.66025),
+            ((1.0, 1.0, -5.0), (-0.5, -1.0, 1.0), 4.55006, 49.44994),
+        ] {
+            let xs = c.local_intersect(&ray(origin, direction));
+            assert_eq!(xs.len(), 2);
+            // Tangent and far-away hits lose precision with f32
+            assert_approx_eq!(xs[0].t, t0, 0.001);
+            assert_approx_eq!(xs[1].t, t1, 0.001);
+        }
+    }
+
+    #[test]
+    fn test_ray_parallel_to_one_half() {
+        let c = Cone::new();
+        let xs = c.local_intersect(&ray((0.0, 0.0, -1.0), (0.0, 1.0, 1.0)));
+        assert_eq!(xs.len(), 1);
+        assert_approx_eq!(xs[0].t, 0.35355);
+    }
+
+    #[test]
+    fn test_scaled_ray_hits() {
+        let mut c = Cone::new();
+        c.set_transform(scaling(200.0, 200.0, 200.0)).unwrap();
+        let r = Ray::new(Point::new(0.0, 100.0, -1000.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 900.0, 0.01);
+        assert_approx_eq!(xs[1].t, 1100.0, 0.01);
+    }
+
+    #[test]
+    fn test_capped() {
+        let c = Cone::truncated(-0.5, 0.5, true);
+        for (origin, direction, count) in [
+            ((0.0, 0.0, -5.0), (0.0, 1.0, 0.0), 0),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 1.0), 2),
+            ((0.0, 0.0, -0.25), (0.0, 1.0, 0.0), 4),
+        ] {
+            assert_eq!(c.local_intersect(&ray(origin, direction)).len(), count);
+        }
+    }
+
+    #[test]
+    fn test_normal() {
+        let c = Cone::new();
+        let sqrt2 = CoordValue::sqrt(2.0);
+        for (point, normal) in [
+            ((1.0, 1.0, 1.0), (1.0, -sqrt2, 1.0)),
+            ((-1.0, -1.0, 0.0), (-1.0, 1.0, 0.0)),
+        ] {
+            assert_approx_eq!(
+                c.local_normal_at(&Point::from_tuple(point)),
+                Normal::from_vector(&Vector::from_tuple(normal))
+            );
+        }
+    }
+
+    #[test]
+    fn test_normal_on_caps() {
+        let c = Cone::truncated(-1.0, 2.0, true);
+        assert_eq!(
+            c.local_normal_at(&Point::new(0.5, 2.0, 0.0)),
+            Normal::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            c.local_normal_at(&Point::new(0.0, -1.0, 0.5)),
+            Normal::new(0.0, -1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_bounds() {
+        let b = Cone::new().local_bounds();
+        let inf = CoordValue::INFINITY;
+        assert_eq!(b.min, Point::new(-inf, -inf, -inf));
+        assert_eq!(b.max, Point::new(inf, inf, inf));
+        let b = Cone::truncated(-5.0, 3.0, false).local_bounds();
+        assert_eq!(b.min, Point::new(-5.0, -5.0, -5.0));
+        assert_eq!(b.max, Point::new(5.0, 3.0, 5.0));
+    }
+}
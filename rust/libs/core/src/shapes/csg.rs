@@ -0,0 +1,365 @@
+use crate::bounds::Bounds;
+use crate::bvh::BvhConfig;
+use crate::fixed_matrix::Matrix4;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersections, Ray};
+use crate::shapes::{Shape, ShapeProperties};
+
+/// How a [`Csg`] combines its two shapes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgOperation {
+    /// Everything in either shape.
+    Union,
+    /// Only what is in both shapes.
+    Intersection,
+    /// What is in the left shape but not in the right one.
+    Difference,
+}
+
+impl CsgOperation {
+    /// Whether an intersection with the left shape (`left_hit`) or the right
+    /// one is on the surface of the combination, given whether it happens
+    /// inside the left shape and inside the right one.
+    pub fn intersection_allowed(self, left_hit: bool, in_left: bool, in_right: bool) -> bool {
+        match self {
+            CsgOperation::Union => (left_hit && !in_right) || (!left_hit && !in_left),
+            CsgOperation::Intersection => (left_hit && in_right) || (!left_hit && in_left),
+            CsgOperation::Difference => (left_hit && !in_right) || (!left_hit && in_left),
+        }
+    }
+}
+
+/// Constructive solid geometry: two shapes combined by a [`CsgOperation`].
+/// Both shapes must be closed, so that rays alternately enter and leave them.
+///
+/// Rays are only tested against the shapes when they cross a box enclosing
+/// both, which is computed once when the combination is built.
+#[derive(Debug)]
+pub struct Csg {
+    properties: ShapeProperties,
+    operation: CsgOperation,
+    left: Box<dyn Shape>,
+    right: Box<dyn Shape>,
+    bounds: Bounds,
+}
+
+impl Csg {
+    pub fn new<L: Shape + 'static, R: Shape + 'static>(
+        operation: CsgOperation,
+        left: L,
+        right: R,
+    ) -> Csg {
+        Csg::from_boxed(operation, Box::new(left), Box::new(right))
+    }
+
+    pub fn from_boxed(
+        operation: CsgOperation,
+        mut left: Box<dyn Shape>,
+        mut right: Box<dyn Shape>,
+    ) -> Csg {
+        let properties = ShapeProperties::new();
+        left.set_parent_inverse(properties.world_inverse);
+        right.set_parent_inverse(properties.world_inverse);
+        let mut bounds = left.parent_space_bounds();
+        bounds.merge(&right.parent_space_bounds());
+        Csg {
+            properties,
+            operation,
+            left,
+            right,
+            bounds,
+        }
+    }
+
+    pub fn union<L: Shape + 'static, R: Shape + 'static>(left: L, right: R) -> Csg {
+        Csg::new(CsgOperation::Union, left, right)
+    }
+
+    pub fn intersection<L: Shape + 'static, R: Shape + 'static>(left: L, right: R) -> Csg {
+        Csg::new(CsgOperation::Intersection, left, right)
+    }
+
+    pub fn difference<L: Shape + 'static, R: Shape + 'static>(left: L, right: R) -> Csg {
+        Csg::new(CsgOperation::Difference, left, right)
+    }
+
+    pub fn operation(&self) -> CsgOperation {
+        self.operation
+    }
+
+    pub fn left(&self) -> &dyn Shape {
+        self.left.as_ref()
+    }
+
+    pub fn right(&self) -> &dyn Shape {
+        self.right.as_ref()
+    }
+
+    /// Keeps the intersections, with either shape, that lie on the surface of
+    /// the combination.
+    pub fn filter_intersections<'a>(
+        &self,
+        xs: Intersections<'a, dyn Shape>,
+    ) -> Intersections<'a, dyn Shape> {
+        let mut in_left = false;
+        let mut in_right = false;
+        xs.into_iter()
+            .filter(|i| {
+                let left_hit = self.left.includes(i.object);
+                let allowed = self
+                    .operation
+                    .intersection_allowed(left_hit, in_left, in_right);
+                // Each intersection enters or leaves the shape it belongs to
+                if left_hit {
+                    in_left = !in_left;
+                } else {
+                    in_right = !in_right;
+                }
+                allowed
+            })
+            .collect()
+    }
+}
+
+impl Shape for Csg {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        if !self.bounds.intersects(ray) {
+            return Intersections::new();
+        }
+        let mut xs = self.left.intersect(ray);
+        xs.extend(self.right.intersect(ray));
+        self.filter_intersections(xs)
+    }
+
+    /// Like groups, a combination has no surface of its own and, asked
+    /// directly, gives the normal of the nearest face of its bounds.
+    fn local_normal_at(&self, point: &Point) -> Normal {
+        self.bounds.normal_at(point)
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        self.bounds.clone()
+    }
+
+    fn build_bvh(&mut self, config: &BvhConfig) {
+        self.left.build_bvh(config);
+        self.right.build_bvh(config);
+    }
+
+    fn set_parent_inverse(&mut self, parent_inverse: Matrix4) {
+        self.properties.set_parent_inverse(parent_inverse);
+        let world_inverse = *self.world_inverse();
+        self.left.set_parent_inverse(world_inverse);
+        self.right.set_parent_inverse(world_inverse);
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        std::ptr::addr_eq(self as *const Csg, other as *const dyn Shape)
+            || self.left.includes(other)
+            || self.right.includes(other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::ray::Intersection;
+    use crate::shapes::{Cube, Sphere};
+    use crate::transform::translation;
+    use crate::tuple::CoordValue;
+    use crate::vector::Vector;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_new() {
+        let c = Csg::union(Sphere::new(), Cube::new());
+        assert_eq!(c.operation(), CsgOperation::Union);
+        assert!(format!("{:?}", c.left()).starts_with("Sphere"));
+        assert!(format!("{:?}", c.right()).starts_with("Cube"));
+    }
+
+    #[test]
+    fn test_intersection_allowed() {
+        use CsgOperation::*;
+        // operation, left_hit, in_left, in_right, expected
+        let rules = [
+            (Union, true, true, true, false),
+            (Union, true, true, false, true),
+            (Union, true, false, true, false),
+            (Union, true, false, false, true),
+            (Union, false, true, true, false),
+            (Union, false, true, false, false),
+            (Union, false, false, true, true),
+            (Union, false, false, false, true),
+            (Intersection, true, true, true, true),
+            (Intersection, true, true, false, false),
+            (Intersection, true, false, true, true),
+            (Intersection, true, false, false, false),
+            (Intersection, false, true, true, true),
+            (Intersection, false, true, false, true),
+            (Intersection, false, false, true, false),
+            (Intersection, false, false, false, false),
+            (Difference, true, true, true, false),
+            (Difference, true, true, false, true),
+            (Difference, true, false, true, false),
+            (Difference, true, false, false, true),
+            (Difference, false, true, true, true),
+            (Difference, false, true, false, true),
+            (Difference, false, false, true, false),
+            (Difference, false, false, false, false),
+        ];
+        for (operation, left_hit, in_left, in_right, expected) in rules {
+            assert_eq!(
+                operation.intersection_allowed(left_hit, in_left, in_right),
+                expected,
+                "{operation:?} {left_hit} {in_left} {in_right}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_filter_intersections() {
+        for (operation, kept) in [
+            (CsgOperation::Union, [0, 3]),
+            (CsgOperation::Intersection, [1, 2]),
+            (CsgOperation::Difference, [0, 1]),
+        ] {
+            let c = Csg::new(operation, Sphere::new(), Cube::new());
+            let xs: Vec<Intersection<dyn Shape>> = vec![
+                Intersection::new(1.0, c.left.as_ref()),
+                Intersection::new(2.0, c.right.as_ref()),
+                Intersection::new(3.0, c.left.as_ref()),
+                Intersection::new(4.0, c.right.as_ref()),
+            ];
+            let result = c.filter_intersections(Intersections::from_vec(xs.clone()));
+            assert_eq!(result.as_slice(), [xs[kept[0]], xs[kept[1]]]);
+        }
+    }
+
+    #[test]
+    fn test_ray_misses() {
+        let c = Csg::union(Sphere::new(), Cube::new());
+        let r = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(c.local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_hits() {
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(0.0, 0.0, 0.5)).unwrap();
+        let c = Csg::union(Sphere::new(), s2);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.local_intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 4.0);
+        assert!(std::ptr::addr_eq(xs[0].object, c.left()));
+        assert_approx_eq!(xs[1].t, 6.5);
+        assert!(std::ptr::addr_eq(xs[1].object, c.right()));
+    }
+
+    #[test]
+    fn test_nested_shapes_are_included() {
+        let inner = Csg::difference(Cube::new(), Sphere::new());
+        let c = Csg::intersection(inner, Sphere::new());
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.left().intersect(&r);
+        assert!(xs.iter().all(|i| c.left().includes(i.object)));
+        assert!(xs.iter().all(|i| c.includes(i.object)));
+        assert!(xs.iter().all(|i| !c.right().includes(i.object)));
+    }
+
+    #[test]
+    fn test_transform_reaches_shapes() {
+        let mut c = Csg::difference(Cube::new(), Sphere::new());
+        c.set_transform(translation(0.0, 0.0, 10.0)).unwrap();
+        let r = Ray::new(Point::new(0.5, 0.5, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.intersect(&r);
+        let ts: Vec<CoordValue> = xs.iter().map(|i| i.t).collect();
+        // Enters the cube, then leaves it for the hollow carved by the sphere
+        assert_eq!(ts.len(), 4);
+        assert_approx_eq!(ts[0], 9.0);
+        let hit = xs.hit().unwrap();
+        assert_approx_eq!(
+            hit.object.normal_at(&r.position(hit.t)),
+            Normal::new(0.0, 0.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn test_normal_is_that_of_the_bounds() {
+        let mut right = Sphere::new();
+        right.set_transform(translation(2.0, 0.0, 0.0)).unwrap();
+        let c = Csg::union(Sphere::new(), right);
+        assert_eq!(
+            c.normal_at(&Point::new(2.8, 0.0, 0.0)),
+            Normal::new(1.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_bounds_contain_both_shapes() {
+        let mut right = Sphere::new();
+        right.set_transform(translation(2.0, 3.0, 4.0)).unwrap();
+        let c = Csg::difference(Sphere::new(), right);
+        let b = c.local_bounds();
+        assert_eq!(b.min, Point::new(-1.0, -1.0, -1.0));
+        assert_eq!(b.max, Point::new(3.0, 4.0, 5.0));
+    }
+
+    /// A unit sphere that counts how often its bounds were asked for.
+    #[derive(Debug, Default)]
+    struct BoundsCountingSphere {
+        sphere: Sphere,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl Shape for BoundsCountingSphere {
+        fn properties(&self) -> &ShapeProperties {
+            self.sphere.properties()
+        }
+
+        fn properties_mut(&mut self) -> &mut ShapeProperties {
+            self.sphere.properties_mut()
+        }
+
+        fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+            self.sphere.local_intersect(ray)
+        }
+
+        fn local_normal_at(&self, point: &Point) -> Normal {
+            self.sphere.local_normal_at(point)
+        }
+
+        fn local_bounds(&self) -> Bounds {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.sphere.local_bounds()
+        }
+    }
+
+    #[test]
+    fn test_bounds_are_cached() {
+        // On the right, as intersections report the wrapped sphere
+        let mut right = BoundsCountingSphere::default();
+        right.set_transform(translation(0.0, 0.0, 0.5)).unwrap();
+        let count = right.count.clone();
+        let c = Csg::union(Sphere::new(), right);
+        let computed = count.load(Ordering::Relaxed);
+        let hit = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let miss = Ray::new(Point::new(0.0, 5.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(c.intersect(&hit).len(), 2);
+        assert!(c.intersect(&miss).is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), computed);
+        assert_eq!(c.local_bounds().max, Point::new(1.0, 1.0, 1.5));
+    }
+}
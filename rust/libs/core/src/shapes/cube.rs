@@ -0,0 +1,144 @@
+use crate::bounds::{check_axis, Bounds};
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use crate::shapes::{Shape, ShapeProperties};
+
+/// The axis-aligned cube spanning -1 to 1 on every axis.
+#[derive(Debug, Clone, Default)]
+pub struct Cube {
+    properties: ShapeProperties,
+}
+
+impl Cube {
+    pub fn new() -> Cube {
+        Cube::default()
+    }
+}
+
+impl Shape for Cube {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        let (xtmin, xtmax) = check_axis(ray.origin.x(), ray.direction.x(), -1.0, 1.0);
+        let (ytmin, ytmax) = check_axis(ray.origin.y(), ray.direction.y(), -1.0, 1.0);
+        let (ztmin, ztmax) = check_axis(ray.origin.z(), ray.direction.z(), -1.0, 1.0);
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+        if tmin > tmax {
+            return Intersections::new();
+        }
+        Intersections::from_vec(vec![
+            Intersection::new(tmin, self as &dyn Shape),
+            Intersection::new(tmax, self as &dyn Shape),
+        ])
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Normal {
+        // The face is the one of the largest component
+        let (x, y, z) = point.as_coordinates();
+        let max = x.abs().max(y.abs()).max(z.abs());
+        if max == x.abs() {
+            Normal::new(x, 0.0, 0.0)
+        } else if max == y.abs() {
+            Normal::new(0.0, y, 0.0)
+        } else {
+            Normal::new(0.0, 0.0, z)
+        }
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        Bounds::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vector::Vector;
+
+    #[test]
+    fn test_ray_intersects_cube() {
+        let c = Cube::new();
+        let cases = [
+            ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), 4.0, 6.0),
+            ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), 4.0, 6.0),
+            ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), 4.0, 6.0),
+            ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), 4.0, 6.0),
+            ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), -1.0, 1.0),
+        ];
+        for (origin, direction, t1, t2) in cases {
+            let r = Ray::new(Point::from_tuple(origin), Vector::from_tuple(direction));
+            let xs = c.local_intersect(&r);
+            assert_eq!(xs.len(), 2, "{origin:?}");
+            assert_eq!(xs[0].t, t1, "{origin:?}");
+            assert_eq!(xs[1].t, t2, "{origin:?}");
+        }
+    }
+
+    #[test]
+    fn test_ray_intersects_hugely_scaled_cube() {
+        let mut c = Cube::new();
+        c.set_transform(crate::transform::scaling(1e4, 1e4, 1e4))
+            .unwrap();
+        let r = Ray::new(
+            Point::new(1.2e4, 0.0, -5e4),
+            Vector::new(-0.3, 0.0, 1.0).normalize(),
+        );
+        assert_eq!(c.intersect(&r).len(), 2);
+    }
+
+    #[test]
+    fn test_ray_misses_cube() {
+        let c = Cube::new();
+        let cases = [
+            ((-2.0, 0.0, 0.0), (0.2673, 0.5345, 0.8018)),
+            ((0.0, -2.0, 0.0), (0.8018, 0.2673, 0.5345)),
+            ((0.0, 0.0, -2.0), (0.5345, 0.8018, 0.2673)),
+            ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0)),
+            ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0)),
+            ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+        for (origin, direction) in cases {
+            let r = Ray::new(Point::from_tuple(origin), Vector::from_tuple(direction));
+            assert!(c.local_intersect(&r).is_empty(), "{origin:?}");
+        }
+    }
+
+    #[test]
+    fn test_normal_on_surface() {
+        let c = Cube::new();
+        let cases = [
+            ((1.0, 0.5, -0.8), (1.0, 0.0, 0.0)),
+            ((-1.0, -0.2, 0.9), (-1.0, 0.0, 0.0)),
+            ((-0.4, 1.0, -0.1), (0.0, 1.0, 0.0)),
+            ((0.3, -1.0, -0.7), (0.0, -1.0, 0.0)),
+            ((-0.6, 0.3, 1.0), (0.0, 0.0, 1.0)),
+            ((0.4, 0.4, -1.0), (0.0, 0.0, -1.0)),
+            ((1.0, 1.0, 1.0), (1.0, 0.0, 0.0)),
+            ((-1.0, -1.0, -1.0), (-1.0, 0.0, 0.0)),
+        ];
+        for (point, normal) in cases {
+            assert_eq!(
+                c.local_normal_at(&Point::from_tuple(point)),
+                Normal::from_vector(&Vector::from_tuple(normal)),
+                "{point:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let b = Cube::new().local_bounds();
+        assert_eq!(b.min, Point::new(-1.0, -1.0, -1.0));
+        assert_eq!(b.max, Point::new(1.0, 1.0, 1.0));
+    }
+}
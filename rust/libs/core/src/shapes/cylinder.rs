@@ -0,0 +1,267 @@
+use crate::approx::EPSILON;
+use crate::bounds::Bounds;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use crate::shapes::{Shape, ShapeProperties};
+use crate::tuple::CoordValue;
+
+/// A cylinder of radius 1 around the y axis, extending from `minimum` to
+/// `maximum` (both excluded) and optionally capped at both ends.
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    properties: ShapeProperties,
+    pub minimum: CoordValue,
+    pub maximum: CoordValue,
+    pub closed: bool,
+}
+
+impl Cylinder {
+    /// An infinite cylinder.
+    pub fn new() -> Cylinder {
+        Cylinder::truncated(CoordValue::NEG_INFINITY, CoordValue::INFINITY, false)
+    }
+
+    pub fn truncated(minimum: CoordValue, maximum: CoordValue, closed: bool) -> Cylinder {
+        Cylinder {
+            properties: ShapeProperties::new(),
+            minimum,
+            maximum,
+            closed,
+        }
+    }
+
+    fn intersect_caps<'a>(&'a self, ray: &Ray, xs: &mut Intersections<'a, dyn Shape>) {
+        // Relative to the direction's length, which scaling the shape shrinks
+        if !self.closed || ray.direction.y().abs() <= EPSILON * ray.direction.magnitude() {
+            return;
+        }
+        for y in [self.minimum, self.maximum] {
+            let t = (y - ray.origin.y()) / ray.direction.y();
+            if within_radius(ray, t, 1.0) {
+                xs.push(Intersection::new(t, self as &dyn Shape));
+            }
+        }
+    }
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Cylinder::new()
+    }
+}
+
+/// Whether the ray at `t` lies within `radius` of the y axis, as cap
+/// intersections must. Rays through the very rim of a cap are counted in
+/// despite rounding errors.
+pub(crate) fn within_radius(ray: &Ray, t: CoordValue, radius: CoordValue) -> bool {
+    let x = ray.origin.x() + t * ray.direction.x();
+    let z = ray.origin.z() + t * ray.direction.z();
+    x * x + z * z <= radius * radius + EPSILON
+}
+
+impl Shape for Cylinder {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        let mut xs = Intersections::new();
+        let (dx, dz) = (ray.direction.x(), ray.direction.z());
+        let (ox, oz) = (ray.origin.x(), ray.origin.z());
+        let a = dx * dx + dz * dz;
+        // Rays parallel to the y axis can only hit the caps
+        if a.abs() > EPSILON * ray.direction.dot(&ray.direction) {
+            let b = 2.0 * (ox * dx + oz * dz);
+            let c = ox * ox + oz * oz - 1.0;
+            let discriminant = b * b - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return xs;
+            }
+            let sqrt_discriminant = CoordValue::sqrt(discriminant);
+            for t in [
+                (-b - sqrt_discriminant) / (2.0 * a),
+                (-b + sqrt_discriminant) / (2.0 * a),
+            ] {
+                let y = ray.origin.y() + t * ray.direction.y();
+                if self.minimum < y && y < self.maximum {
+                    xs.push(Intersection::new(t, self as &dyn Shape));
+                }
+            }
+        }
+        self.intersect_caps(ray, &mut xs);
+        xs
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Normal {
+        let (x, y, z) = point.as_coordinates();
+        let distance = x * x + z * z;
+        if distance < 1.0 && y >= self.maximum - EPSILON {
+            Normal::new(0.0, 1.0, 0.0)
+        } else if distance < 1.0 && y <= self.minimum + EPSILON {
+            Normal::new(0.0, -1.0, 0.0)
+        } else {
+            Normal::new(x, 0.0, z)
+        }
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new(-1.0, self.minimum, -1.0),
+            Point::new(1.0, self.maximum, 1.0),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::scaling;
+    use crate::vector::Vector;
+
+    fn ray(
+        origin: (CoordValue, CoordValue, CoordValue),
+        direction: (CoordValue, CoordValue, CoordValue),
+    ) -> Ray {
+        Ray::new(
+            Point::from_tuple(origin),
+            Vector::from_tuple(direction).normalize(),
+        )
+    }
+
+    #[test]
+    fn test_default_is_infinite_and_open() {
+        let c = Cylinder::new();
+        assert_eq!(c.minimum, CoordValue::NEG_INFINITY);
+        assert_eq!(c.maximum, CoordValue::INFINITY);
+        assert!(!c.closed);
+    }
+
+    #[test]
+    fn test_ray_misses() {
+        let c = Cylinder::new();
+        for (origin, direction) in [
+            ((1.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 0.0, -5.0), (1.0, 1.0, 1.0)),
+        ] {
+            assert!(c.local_intersect(&ray(origin, direction)).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_ray_hits() {
+        let c = Cylinder::new();
+        for (origin, direction, t0, t1) in [
+            ((1.0, 0.0, -5.0), (0.0, 0.0, 1.0), 5.0, 5.0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.1, 1.0, 1.0), 6.80798, 7.08872),
+        ] {
+            let xs = c.local_intersect(&ray(origin, direction));
+            assert_eq!(xs.len(), 2);
+            assert_approx_eq!(xs[0].t, t0, 0.0001);
+            assert_approx_eq!(xs[1].t, t1, 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_scaled_ray_hits() {
+        let mut c = Cylinder::new();
+        c.set_transform(scaling(200.0, 200.0, 200.0)).unwrap();
+        let r = Ray::new(Point::new(0.0, 0.0, -1000.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 800.0, 0.01);
+        assert_approx_eq!(xs[1].t, 1200.0, 0.01);
+    }
+
+    #[test]
+    fn test_normal() {
+        let c = Cylinder::new();
+        for (point, normal) in [
+            ((1.0, 0.0, 0.0), (1.0, 0.0, 0.0)),
+            ((0.0, 5.0, -1.0), (0.0, 0.0, -1.0)),
+            ((0.0, -2.0, 1.0), (0.0, 0.0, 1.0)),
+            ((-1.0, 1.0, 0.0), (-1.0, 0.0, 0.0)),
+        ] {
+            assert_eq!(
+                c.local_normal_at(&Point::from_tuple(point)),
+                Normal::from_vector(&Vector::from_tuple(normal))
+            );
+        }
+    }
+
+    #[test]
+    fn test_truncated() {
+        let c = Cylinder::truncated(1.0, 2.0, false);
+        for (origin, direction, count) in [
+            ((0.0, 1.5, 0.0), (0.1, 1.0, 0.0), 0),
+            ((0.0, 3.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 0.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 2.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.0, -5.0), (0.0, 0.0, 1.0), 0),
+            ((0.0, 1.5, -2.0), (0.0, 0.0, 1.0), 2),
+        ] {
+            assert_eq!(c.local_intersect(&ray(origin, direction)).len(), count);
+        }
+    }
+
+    #[test]
+    fn test_capped() {
+        let c = Cylinder::truncated(1.0, 2.0, true);
+        for (origin, direction, count) in [
+            ((0.0, 3.0, 0.0), (0.0, -1.0, 0.0), 2),
+            ((0.0, 3.0, -2.0), (0.0, -1.0, 2.0), 2),
+            ((0.0, 4.0, -2.0), (0.0, -1.0, 1.0), 2),
+            ((0.0, 0.0, -2.0), (0.0, 1.0, 2.0), 2),
+            ((0.0, -1.0, -2.0), (0.0, 1.0, 1.0), 2),
+        ] {
+            assert_eq!(c.local_intersect(&ray(origin, direction)).len(), count);
+        }
+    }
+
+    #[test]
+    fn test_scaled_capped() {
+        let mut c = Cylinder::truncated(1.0, 2.0, true);
+        c.set_transform(scaling(100000.0, 100000.0, 100000.0))
+            .unwrap();
+        let r = Ray::new(Point::new(0.0, 300000.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let xs = c.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert_approx_eq!(xs[0].t, 100000.0, 1.0);
+        assert_approx_eq!(xs[1].t, 200000.0, 1.0);
+    }
+
+    #[test]
+    fn test_normal_on_caps() {
+        let c = Cylinder::truncated(1.0, 2.0, true);
+        for (point, normal) in [
+            ((0.0, 1.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.5, 1.0, 0.0), (0.0, -1.0, 0.0)),
+            ((0.0, 1.0, 0.5), (0.0, -1.0, 0.0)),
+            ((0.0, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.5, 2.0, 0.0), (0.0, 1.0, 0.0)),
+            ((0.0, 2.0, 0.5), (0.0, 1.0, 0.0)),
+        ] {
+            assert_eq!(
+                c.local_normal_at(&Point::from_tuple(point)),
+                Normal::from_vector(&Vector::from_tuple(normal))
+            );
+        }
+    }
+
+    #[test]
+    fn test_bounds() {
+        let b = Cylinder::new().local_bounds();
+        assert_eq!(b.min, Point::new(-1.0, CoordValue::NEG_INFINITY, -1.0));
+        assert_eq!(b.max, Point::new(1.0, CoordValue::INFINITY, 1.0));
+        let b = Cylinder::truncated(-5.0, 3.0, false).local_bounds();
+        assert_eq!(b.min, Point::new(-1.0, -5.0, -1.0));
+        assert_eq!(b.max, Point::new(1.0, 3.0, 1.0));
+    }
+}
@@ -0,0 +1,455 @@
+use crate::bounds::Bounds;
+use crate::bvh::{Bvh, BvhConfig};
+use crate::fixed_matrix::Matrix4;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersections, Ray};
+use crate::shapes::{Shape, ShapeProperties};
+
+/// A collection of shapes transformed as one. Children are positioned
+/// relative to the group, and groups can be nested.
+///
+/// Rays are only tested against the children when they cross the bounds of
+/// the group; [`Shape::build_bvh`] narrows this down to the children whose
+/// own bounds they cross.
+#[derive(Debug, Default)]
+pub struct Group {
+    properties: ShapeProperties,
+    children: Vec<Box<dyn Shape>>,
+    bounds: Bounds,
+    bvh: Option<Bvh>,
+}
+
+impl Group {
+    pub fn new() -> Group {
+        Group::default()
+    }
+
+    /// The children of the group, reordered when building a BVH.
+    pub fn children(&self) -> &[Box<dyn Shape>] {
+        &self.children
+    }
+
+    /// The hierarchy built by [`Shape::build_bvh`], until a child is added.
+    pub fn bvh(&self) -> Option<&Bvh> {
+        self.bvh.as_ref()
+    }
+
+    pub fn add_child<S: Shape + 'static>(&mut self, child: S) {
+        self.add_boxed_child(Box::new(child));
+    }
+
+    pub fn add_boxed_child(&mut self, mut child: Box<dyn Shape>) {
+        child.set_parent_inverse(*self.world_inverse());
+        self.bounds.merge(&child.parent_space_bounds());
+        self.children.push(child);
+        self.bvh = None;
+    }
+}
+
+impl Shape for Group {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        let mut xs = Intersections::new();
+        match &self.bvh {
+            Some(bvh) => bvh.traverse(ray, |range| {
+                xs.extend(
+                    self.children[range]
+                        .iter()
+                        .flat_map(|child| child.intersect(ray)),
+                );
+            }),
+            None if self.bounds.intersects(ray) => {
+                xs.extend(self.children.iter().flat_map(|child| child.intersect(ray)));
+            }
+            None => {}
+        }
+        xs
+    }
+
+    /// Groups have no surface of their own: intersections, and so the normals
+    /// used for shading, always belong to one of their children. Asked
+    /// directly, a group gives the normal of the nearest face of its bounds.
+    fn local_normal_at(&self, point: &Point) -> Normal {
+        self.bounds.normal_at(point)
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        self.bounds.clone()
+    }
+
+    fn build_bvh(&mut self, config: &BvhConfig) {
+        for child in self.children.iter_mut() {
+            child.build_bvh(config);
+        }
+        let bounds: Vec<Bounds> = self
+            .children
+            .iter()
+            .map(|child| child.parent_space_bounds())
+            .collect();
+        let (bvh, order) = Bvh::build(&bounds, config);
+        let mut children: Vec<Option<Box<dyn Shape>>> = std::mem::take(&mut self.children)
+            .into_iter()
+            .map(Some)
+            .collect();
+        self.children = order
+            .into_iter()
+            .map(|index| children[index].take().unwrap())
+            .collect();
+        self.bvh = Some(bvh);
+    }
+
+    fn set_parent_inverse(&mut self, parent_inverse: Matrix4) {
+        self.properties.set_parent_inverse(parent_inverse);
+        let world_inverse = *self.world_inverse();
+        for child in self.children.iter_mut() {
+            child.set_parent_inverse(world_inverse);
+        }
+    }
+
+    fn includes(&self, other: &dyn Shape) -> bool {
+        std::ptr::addr_eq(self as *const Group, other as *const dyn Shape)
+            || self.children.iter().any(|child| child.includes(other))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::shapes::{Cylinder, Sphere, TestShape};
+    use crate::transform::{rotation_y, scaling, translation};
+    use crate::tuple::CoordValue;
+    use crate::vector::Vector;
+    use std::any::Any;
+    use std::f64::consts::PI;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_new_group_is_empty() {
+        let g = Group::new();
+        assert_eq!(*g.transform(), Matrix4::identity());
+        assert!(g.children().is_empty());
+    }
+
+    #[test]
+    fn test_add_child() {
+        let mut g = Group::new();
+        g.add_child(TestShape::new());
+        assert_eq!(g.children().len(), 1);
+    }
+
+    #[test]
+    fn test_includes_descendants() {
+        let mut inner = Group::new();
+        inner.add_child(Sphere::new());
+        let mut g = Group::new();
+        g.add_child(inner);
+        let inner = &g.children()[0];
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let sphere = g.intersect(&r)[0].object;
+        assert!(g.includes(&g));
+        assert!(g.includes(inner.as_ref()));
+        assert!(g.includes(sphere));
+        assert!(inner.includes(sphere));
+        assert!(!sphere.includes(inner.as_ref()));
+        assert!(!g.includes(&Sphere::new()));
+    }
+
+    #[test]
+    fn test_normal_is_that_of_the_bounds() {
+        let mut g = Group::new();
+        assert_eq!(
+            g.normal_at(&Point::new(0.0, 0.0, 0.0)),
+            Normal::new(0.0, 1.0, 0.0)
+        );
+        g.set_transform(translation(0.0, 0.0, 10.0)).unwrap();
+        g.add_child(Sphere::new());
+        assert_eq!(
+            g.normal_at(&Point::new(0.2, 0.1, 9.1)),
+            Normal::new(0.0, 0.0, -1.0)
+        );
+    }
+
+    #[test]
+    fn test_intersect_empty_group() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(Group::new().local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_intersect_nonempty_group() {
+        let mut g = Group::new();
+        g.add_child(Sphere::new());
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(0.0, 0.0, -3.0)).unwrap();
+        g.add_child(s2);
+        let mut s3 = Sphere::new();
+        s3.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        g.add_child(s3);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = g.local_intersect(&r);
+        let ts: Vec<CoordValue> = xs.iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![1.0, 3.0, 4.0, 6.0]);
+        let children = g.children();
+        for (i, child) in [1, 1, 0, 0].into_iter().enumerate() {
+            assert!(std::ptr::addr_eq(xs[i].object, children[child].as_ref()));
+        }
+    }
+
+    #[test]
+    fn test_intersect_transformed_group() {
+        let mut g = Group::new();
+        g.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let mut s = Sphere::new();
+        s.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        g.add_child(s);
+        let r = Ray::new(Point::new(10.0, 0.0, -10.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(g.intersect(&r).len(), 2);
+    }
+
+    #[test]
+    fn test_intersect_hugely_scaled_group() {
+        let mut direct = Sphere::new();
+        direct.set_transform(scaling(1e4, 1e4, 1e4)).unwrap();
+        let mut g = Group::new();
+        g.set_transform(scaling(1e4, 1e4, 1e4)).unwrap();
+        g.add_child(Sphere::new());
+        let r = Ray::new(
+            Point::new(1.2e4, 0.0, -5e4),
+            Vector::new(-0.3, 0.0, 1.0).normalize(),
+        );
+        assert_eq!(direct.intersect(&r).len(), 2);
+        assert_eq!(g.intersect(&r).len(), 2);
+    }
+
+    /// A sphere moved by 5 along x, in a group scaled by `scale`, itself in a
+    /// group rotated around y. The outer transform is set either before or
+    /// after the inner group is added, which must not make a difference.
+    fn nested_sphere(scale: (f64, f64, f64), transform_first: bool) -> Group {
+        let mut s = Sphere::new();
+        s.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        let mut g2 = Group::new();
+        g2.set_transform(scaling(scale.0, scale.1, scale.2))
+            .unwrap();
+        g2.add_child(s);
+        let mut g1 = Group::new();
+        if transform_first {
+            g1.set_transform(rotation_y(PI / 2.0)).unwrap();
+            g1.add_child(g2);
+        } else {
+            g1.add_child(g2);
+            g1.set_transform(rotation_y(PI / 2.0)).unwrap();
+        }
+        g1
+    }
+
+    /// The sphere of [`nested_sphere`], found by intersecting it: it ends up
+    /// centered on the negative z axis in world space.
+    fn find_sphere(g1: &Group) -> &dyn Shape {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, -1.0));
+        g1.intersect(&r).hit().unwrap().object
+    }
+
+    #[test]
+    fn test_world_to_object() {
+        for transform_first in [true, false] {
+            let g1 = nested_sphere((2.0, 2.0, 2.0), transform_first);
+            let s = find_sphere(&g1);
+            let p = s.world_to_object(&Point::new(-2.0, 0.0, -10.0));
+            assert_approx_eq!(p, Point::new(0.0, 0.0, -1.0));
+        }
+    }
+
+    #[test]
+    fn test_normal_to_world() {
+        let third = (3.0 as CoordValue).sqrt() / 3.0;
+        for transform_first in [true, false] {
+            let g1 = nested_sphere((1.0, 2.0, 3.0), transform_first);
+            let n = find_sphere(&g1).normal_to_world(&Normal::new(third, third, third));
+            assert_approx_eq!(n, Normal::new(0.2857, 0.4286, -0.8571), 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_normal_on_child() {
+        for transform_first in [true, false] {
+            let g1 = nested_sphere((1.0, 2.0, 3.0), transform_first);
+            let n = find_sphere(&g1).normal_at(&Point::new(1.7321, 1.1547, -5.5774));
+            assert_approx_eq!(n, Normal::new(0.2857, 0.4286, -0.8571), 0.0001);
+        }
+    }
+
+    #[test]
+    fn test_set_transform_keeps_parent() {
+        let mut g = Group::new();
+        g.set_transform(translation(0.0, 0.0, 10.0)).unwrap();
+        let mut s = Sphere::new();
+        s.set_parent_inverse(*g.world_inverse());
+        s.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        assert_approx_eq!(
+            s.world_to_object(&Point::new(0.0, 0.0, 12.0)),
+            Point::new(0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_bounds_contain_children() {
+        let mut s = Sphere::new();
+        s.set_transform((&translation(2.0, 5.0, -3.0) * &scaling(2.0, 2.0, 2.0)).unwrap())
+            .unwrap();
+        let mut c = Cylinder::truncated(-2.0, 2.0, false);
+        c.set_transform((&translation(-4.0, -1.0, 4.0) * &scaling(0.5, 1.0, 0.5)).unwrap())
+            .unwrap();
+        let mut g = Group::new();
+        g.add_child(s);
+        g.add_child(c);
+        let b = g.local_bounds();
+        assert_eq!(b.min, Point::new(-4.5, -3.0, -5.0));
+        assert_eq!(b.max, Point::new(4.0, 7.0, 4.5));
+    }
+
+    /// A unit sphere that counts how many rays it was intersected with.
+    #[derive(Debug, Default)]
+    struct CountingSphere {
+        sphere: Sphere,
+        count: Arc<AtomicUsize>,
+    }
+
+    impl Shape for CountingSphere {
+        fn properties(&self) -> &ShapeProperties {
+            self.sphere.properties()
+        }
+
+        fn properties_mut(&mut self) -> &mut ShapeProperties {
+            self.sphere.properties_mut()
+        }
+
+        fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+            self.count.fetch_add(1, Ordering::Relaxed);
+            self.sphere.local_intersect(ray)
+        }
+
+        fn local_normal_at(&self, point: &Point) -> Normal {
+            self.sphere.local_normal_at(point)
+        }
+
+        fn local_bounds(&self) -> Bounds {
+            self.sphere.local_bounds()
+        }
+    }
+
+    #[test]
+    fn test_ray_missing_bounds_skips_children() {
+        let child = CountingSphere::default();
+        let count = child.count.clone();
+        let mut g = Group::new();
+        g.add_child(child);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0));
+        assert!(g.intersect(&r).is_empty());
+        assert_eq!(count.load(Ordering::Relaxed), 0);
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(g.intersect(&r).len(), 2);
+        assert_eq!(count.load(Ordering::Relaxed), 1);
+    }
+
+    #[test]
+    fn test_bvh_skips_children_away_from_ray() {
+        let counts: Vec<Arc<AtomicUsize>> = (0..20).map(|_| Arc::default()).collect();
+        let mut g = Group::new();
+        for (i, count) in counts.iter().enumerate() {
+            let mut child = CountingSphere {
+                count: count.clone(),
+                ..CountingSphere::default()
+            };
+            child
+                .set_transform(translation(i as f64 * 3.0, 0.0, 0.0))
+                .unwrap();
+            g.add_child(child);
+        }
+        g.build_bvh(&BvhConfig::default());
+        let r = Ray::new(Point::new(30.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(g.intersect(&r).len(), 2);
+        let tested: usize = counts
+            .iter()
+            .map(|count| count.load(Ordering::Relaxed))
+            .sum();
+        assert_eq!(counts[10].load(Ordering::Relaxed), 1);
+        assert!(tested <= BvhConfig::default().max_leaf_size);
+    }
+
+    /// A 10x10 grid of small spheres in the xy plane, in a group.
+    fn sphere_grid() -> Group {
+        let mut g = Group::new();
+        for i in 0..100 {
+            let mut s = Sphere::new();
+            let (x, y) = ((i % 10) as f64 * 3.0, (i / 10) as f64 * 3.0);
+            s.set_transform(translation(x, y, 0.0)).unwrap();
+            g.add_child(s);
+        }
+        g
+    }
+
+    #[test]
+    fn test_bvh_gives_same_intersections() {
+        let linear = sphere_grid();
+        let mut g = sphere_grid();
+        g.add_child(crate::shapes::Plane::new());
+        g.build_bvh(&BvhConfig::default());
+        assert_eq!(g.children().len(), 101);
+        for (x, y) in [
+            (0.0, 0.0),
+            (3.0, 6.0),
+            (27.0, 27.0),
+            (1.5, 1.5),
+            (0.5, 13.0),
+        ] {
+            let r = Ray::new(Point::new(x, y, -5.0), Vector::new(0.0, 0.0, 1.0));
+            let ts: Vec<CoordValue> = g.intersect(&r).iter().map(|i| i.t).collect();
+            let expected: Vec<CoordValue> = linear.intersect(&r).iter().map(|i| i.t).collect();
+            assert_eq!(ts, expected);
+        }
+        // The plane is unbounded and always tested
+        let r = Ray::new(Point::new(-50.0, 5.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        assert_eq!(g.intersect(&r).len(), 1);
+    }
+
+    #[test]
+    fn test_bvh_stats() {
+        let mut g = sphere_grid();
+        assert!(g.bvh().is_none());
+        g.build_bvh(&BvhConfig::default());
+        let stats = g.bvh().unwrap().stats();
+        assert_eq!(stats.items, 100);
+        assert!(stats.max_leaf_size <= BvhConfig::default().max_leaf_size);
+        assert!(stats.sah_cost < 100.0);
+        // Adding a child invalidates the hierarchy
+        g.add_child(Sphere::new());
+        assert!(g.bvh().is_none());
+    }
+
+    #[test]
+    fn test_build_bvh_reaches_subgroups() {
+        let mut g = Group::new();
+        g.add_child(sphere_grid());
+        g.build_bvh(&BvhConfig::default());
+        // Subgroups are built before their bounds are partitioned, so the
+        // subgroup's hierarchy is there and the children still line up
+        let r = Ray::new(Point::new(3.0, 6.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let ts: Vec<CoordValue> = g.intersect(&r).iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![4.0, 6.0]);
+        assert_eq!(g.bvh().unwrap().stats().items, 1);
+        let child: &dyn Any = g.children()[0].as_ref();
+        let subgroup = child.downcast_ref::<Group>().unwrap();
+        assert_eq!(subgroup.bvh().unwrap().stats().items, 100);
+    }
+}
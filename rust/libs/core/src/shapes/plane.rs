@@ -0,0 +1,121 @@
+use crate::approx::EPSILON;
+use crate::bounds::Bounds;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use crate::shapes::{Shape, ShapeProperties};
+use crate::tuple::CoordValue;
+
+/// The infinite xz plane.
+#[derive(Debug, Clone, Default)]
+pub struct Plane {
+    properties: ShapeProperties,
+}
+
+impl Plane {
+    pub fn new() -> Plane {
+        Plane::default()
+    }
+}
+
+impl Shape for Plane {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        // A ray parallel to the plane never meets it, and a coplanar one is
+        // treated the same as the plane has no thickness. The tolerance is
+        // relative to the direction's length, which scaling the plane shrinks.
+        if ray.direction.y().abs() <= EPSILON * ray.direction.magnitude() {
+            return Intersections::new();
+        }
+        let t = -ray.origin.y() / ray.direction.y();
+        Intersections::from_vec(vec![Intersection::new(t, self as &dyn Shape)])
+    }
+
+    fn local_normal_at(&self, _point: &Point) -> Normal {
+        Normal::new(0.0, 1.0, 0.0)
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        Bounds::new(
+            Point::new(-CoordValue::INFINITY, 0.0, -CoordValue::INFINITY),
+            Point::new(CoordValue::INFINITY, 0.0, CoordValue::INFINITY),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::scaling;
+    use crate::vector::Vector;
+
+    #[test]
+    fn test_normal_is_constant() {
+        let p = Plane::new();
+        for point in [
+            Point::new(0.0, 0.0, 0.0),
+            Point::new(10.0, 0.0, -10.0),
+            Point::new(-5.0, 0.0, 150.0),
+        ] {
+            assert_eq!(p.local_normal_at(&point), Normal::new(0.0, 1.0, 0.0));
+        }
+    }
+
+    #[test]
+    fn test_intersect_parallel_ray() {
+        let r = Ray::new(Point::new(0.0, 10.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(Plane::new().local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_intersect_coplanar_ray() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(Plane::new().local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_intersect_from_above() {
+        let p = Plane::new();
+        let r = Ray::new(Point::new(0.0, 1.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let xs = p.local_intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &p));
+    }
+
+    #[test]
+    fn test_intersect_from_below() {
+        let p = Plane::new();
+        let r = Ray::new(Point::new(0.0, -1.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        let xs = p.local_intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 1.0);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let b = Plane::new().local_bounds();
+        let inf = CoordValue::INFINITY;
+        assert_eq!(b.min, Point::new(-inf, 0.0, -inf));
+        assert_eq!(b.max, Point::new(inf, 0.0, inf));
+    }
+
+    #[test]
+    fn test_intersect_scaled() {
+        let mut p = Plane::new();
+        p.set_transform(scaling(100000.0, 100000.0, 100000.0))
+            .unwrap();
+        let r = Ray::new(Point::new(0.0, 10.0, 0.0), Vector::new(0.0, -1.0, 0.0));
+        let xs = p.intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_approx_eq!(xs[0].t, 10.0);
+    }
+}
@@ -0,0 +1,210 @@
+use crate::bounds::Bounds;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use crate::shapes::triangle::intersect_triangle;
+use crate::shapes::{Shape, ShapeProperties};
+use crate::tuple::CoordValue;
+use crate::vector::Vector;
+
+/// A triangle with a normal given at each corner, interpolated across its
+/// surface so that meshes of them look smoothly curved.
+#[derive(Debug, Clone)]
+pub struct SmoothTriangle {
+    properties: ShapeProperties,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    n1: Normal,
+    n2: Normal,
+    n3: Normal,
+    e1: Vector,
+    e2: Vector,
+}
+
+impl SmoothTriangle {
+    pub fn new(
+        p1: Point,
+        p2: Point,
+        p3: Point,
+        n1: Normal,
+        n2: Normal,
+        n3: Normal,
+    ) -> SmoothTriangle {
+        let e1 = &p2 - &p1;
+        let e2 = &p3 - &p1;
+        SmoothTriangle {
+            properties: ShapeProperties::new(),
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1,
+            e2,
+        }
+    }
+
+    pub fn p1(&self) -> &Point {
+        &self.p1
+    }
+
+    pub fn p2(&self) -> &Point {
+        &self.p2
+    }
+
+    pub fn p3(&self) -> &Point {
+        &self.p3
+    }
+
+    pub fn n1(&self) -> &Normal {
+        &self.n1
+    }
+
+    pub fn n2(&self) -> &Normal {
+        &self.n2
+    }
+
+    pub fn n3(&self) -> &Normal {
+        &self.n3
+    }
+
+    /// Normal at the point with barycentric coordinates `u`/`v`, the weights
+    /// of `p2` and `p3`.
+    fn interpolate(&self, u: CoordValue, v: CoordValue) -> Normal {
+        let n = &(&(&self.n2.as_vector() * u) + &(&self.n3.as_vector() * v))
+            + &(&self.n1.as_vector() * (1.0 - u - v));
+        Normal::from_vector(&n)
+    }
+
+    /// Barycentric coordinates of a point on the triangle, for when no
+    /// intersection provides them.
+    fn barycentric(&self, point: &Point) -> (CoordValue, CoordValue) {
+        let w = point - &self.p1;
+        let d00 = self.e1.dot(&self.e1);
+        let d01 = self.e1.dot(&self.e2);
+        let d11 = self.e2.dot(&self.e2);
+        let d20 = w.dot(&self.e1);
+        let d21 = w.dot(&self.e2);
+        let denominator = d00 * d11 - d01 * d01;
+        (
+            (d11 * d20 - d01 * d21) / denominator,
+            (d00 * d21 - d01 * d20) / denominator,
+        )
+    }
+}
+
+impl Shape for SmoothTriangle {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        match intersect_triangle(ray, &self.p1, &self.e1, &self.e2) {
+            Some((t, u, v)) => {
+                Intersections::from_vec(vec![Intersection::with_uv(t, self as &dyn Shape, u, v)])
+            }
+            None => Intersections::new(),
+        }
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Normal {
+        let (u, v) = self.barycentric(point);
+        self.interpolate(u, v)
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        let mut bounds = Bounds::empty();
+        for point in [&self.p1, &self.p2, &self.p3] {
+            bounds.add_point(point);
+        }
+        bounds
+    }
+
+    fn local_normal_at_hit(&self, point: &Point, hit: &Intersection<'_, dyn Shape>) -> Normal {
+        match hit.uv {
+            Some((u, v)) => self.interpolate(u, v),
+            None => self.local_normal_at(point),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::world::Computations;
+
+    fn smooth_triangle() -> SmoothTriangle {
+        SmoothTriangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+            Normal::new(0.0, 1.0, 0.0),
+            Normal::new(-1.0, 0.0, 0.0),
+            Normal::new(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let t = smooth_triangle();
+        assert_eq!(*t.p1(), Point::new(0.0, 1.0, 0.0));
+        assert_eq!(*t.p2(), Point::new(-1.0, 0.0, 0.0));
+        assert_eq!(*t.p3(), Point::new(1.0, 0.0, 0.0));
+        assert_eq!(*t.n1(), Normal::new(0.0, 1.0, 0.0));
+        assert_eq!(*t.n2(), Normal::new(-1.0, 0.0, 0.0));
+        assert_eq!(*t.n3(), Normal::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_intersection_stores_uv() {
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let t = smooth_triangle();
+        let xs = t.local_intersect(&r);
+        let (u, v) = xs[0].uv.unwrap();
+        assert_approx_eq!(u, 0.45);
+        assert_approx_eq!(v, 0.25);
+    }
+
+    #[test]
+    fn test_normal_interpolates_with_uv() {
+        let t = smooth_triangle();
+        let i = Intersection::with_uv(1.0, &t as &dyn Shape, 0.45, 0.25);
+        let n = t.normal_at_hit(&Point::new(0.0, 0.0, 0.0), &i);
+        assert_approx_eq!(n, Normal::new(-0.5547, 0.83205, 0.0), 0.0001);
+    }
+
+    #[test]
+    fn test_normal_without_uv_uses_point() {
+        let t = smooth_triangle();
+        let n = t.local_normal_at(&Point::new(-0.2, 0.3, 0.0));
+        assert_approx_eq!(n, Normal::new(-0.5547, 0.83205, 0.0), 0.0001);
+    }
+
+    #[test]
+    fn test_prepare_computations_uses_interpolated_normal() {
+        let t = smooth_triangle();
+        let xs = Intersections::from_vec(vec![Intersection::with_uv(
+            1.0,
+            &t as &dyn Shape,
+            0.45,
+            0.25,
+        )]);
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_approx_eq!(comps.normalv, Normal::new(-0.5547, 0.83205, 0.0), 0.0001);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let b = smooth_triangle().local_bounds();
+        assert_eq!(b.min, Point::new(-1.0, 0.0, 0.0));
+        assert_eq!(b.max, Point::new(1.0, 1.0, 0.0));
+    }
+}
@@ -0,0 +1,222 @@
+use crate::bounds::Bounds;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use crate::shapes::{Shape, ShapeProperties};
+use crate::tuple::CoordValue;
+
+/// A unit sphere centered at the origin.
+#[derive(Debug, Clone, Default)]
+pub struct Sphere {
+    properties: ShapeProperties,
+}
+
+impl Sphere {
+    pub fn new() -> Sphere {
+        Sphere {
+            properties: ShapeProperties::new(),
+        }
+    }
+}
+
+impl Shape for Sphere {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        let sphere_to_ray = &ray.origin - &Point::new(0.0, 0.0, 0.0);
+        let a = ray.direction.dot(&ray.direction);
+        let b = 2.0 * ray.direction.dot(&sphere_to_ray);
+        let c = sphere_to_ray.dot(&sphere_to_ray) - 1.0;
+        let discriminant = b * b - 4.0 * a * c;
+        if discriminant < 0.0 {
+            return Intersections::new();
+        }
+        let sqrt_discriminant = CoordValue::sqrt(discriminant);
+        Intersections::from_vec(vec![
+            Intersection::new((-b - sqrt_discriminant) / (2.0 * a), self as &dyn Shape),
+            Intersection::new((-b + sqrt_discriminant) / (2.0 * a), self as &dyn Shape),
+        ])
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Normal {
+        Normal::from_vector(&(point - &Point::new(0.0, 0.0, 0.0)))
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        Bounds::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::color::Color;
+    use crate::fixed_matrix::Matrix4;
+    use crate::material::Material;
+    use crate::matrix::MatrixError;
+    use crate::transform::{rotation_z, scaling, translation};
+    use crate::tuple::consts::FRAC_1_SQRT_2;
+    use crate::vector::Vector;
+    use std::f64::consts::PI;
+
+    fn ts(xs: &Intersections<'_, dyn Shape>) -> Vec<CoordValue> {
+        xs.iter().map(|i| i.t).collect()
+    }
+
+    #[test]
+    fn test_ray_intersects_at_two_points() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        assert_eq!(ts(&s.intersect(&r)), vec![4.0, 6.0]);
+    }
+
+    #[test]
+    fn test_ray_intersects_at_a_tangent() {
+        let r = Ray::new(Point::new(0.0, 1.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        assert_eq!(ts(&s.intersect(&r)), vec![5.0, 5.0]);
+    }
+
+    #[test]
+    fn test_ray_misses() {
+        let r = Ray::new(Point::new(0.0, 2.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert!(Sphere::new().intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_originates_inside() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(ts(&Sphere::new().intersect(&r)), vec![-1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_sphere_behind_ray() {
+        let r = Ray::new(Point::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_eq!(ts(&Sphere::new().intersect(&r)), vec![-6.0, -4.0]);
+    }
+
+    #[test]
+    fn test_intersect_sets_object() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let s = Sphere::new();
+        let xs = s.intersect(&r);
+        assert_eq!(xs.len(), 2);
+        assert!(std::ptr::addr_eq(xs[0].object, &s));
+        assert!(std::ptr::addr_eq(xs[1].object, &s));
+    }
+
+    #[test]
+    fn test_default_transform_and_material() {
+        let s = Sphere::new();
+        assert_eq!(*s.transform(), Matrix4::identity());
+        assert_eq!(*s.material(), Material::default());
+    }
+
+    #[test]
+    fn test_set_transform_and_material() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(2.0, 3.0, 4.0)).unwrap();
+        assert_eq!(
+            *s.transform(),
+            Matrix4::try_from(translation(2.0, 3.0, 4.0)).unwrap()
+        );
+        assert_eq!(
+            *s.inverse_transform(),
+            Matrix4::try_from(translation(-2.0, -3.0, -4.0)).unwrap()
+        );
+        let m = Material {
+            color: Color::new(1.0, 0.0, 0.0),
+            ambient: 1.0,
+            ..Material::default()
+        };
+        s.set_material(m.clone());
+        assert_eq!(*s.material(), m);
+    }
+
+    #[test]
+    fn test_set_transform_rejects_singular_matrix() {
+        let mut s = Sphere::new();
+        assert_eq!(
+            s.set_transform(scaling(0.0, 1.0, 1.0)),
+            Err(MatrixError::NotInvertible)
+        );
+        assert_eq!(*s.transform(), Matrix4::identity());
+    }
+
+    #[test]
+    fn test_intersect_scaled_sphere() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut s = Sphere::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        assert_eq!(ts(&s.intersect(&r)), vec![3.0, 7.0]);
+    }
+
+    #[test]
+    fn test_intersect_translated_sphere() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut s = Sphere::new();
+        s.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        assert!(s.intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_normal_on_axes() {
+        let s = Sphere::new();
+        assert_eq!(
+            s.normal_at(&Point::new(1.0, 0.0, 0.0)),
+            Normal::new(1.0, 0.0, 0.0)
+        );
+        assert_eq!(
+            s.normal_at(&Point::new(0.0, 1.0, 0.0)),
+            Normal::new(0.0, 1.0, 0.0)
+        );
+        assert_eq!(
+            s.normal_at(&Point::new(0.0, 0.0, 1.0)),
+            Normal::new(0.0, 0.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn test_normal_at_nonaxial_point() {
+        let s = Sphere::new();
+        let k = CoordValue::sqrt(3.0) / 3.0;
+        let n = s.normal_at(&Point::new(k, k, k));
+        assert_approx_eq!(n, Normal::new(k, k, k));
+        assert_approx_eq!(n.as_vector(), n.as_vector().normalize());
+    }
+
+    #[test]
+    fn test_normal_on_translated_sphere() {
+        let mut s = Sphere::new();
+        s.set_transform(translation(0.0, 1.0, 0.0)).unwrap();
+        let n = s.normal_at(&Point::new(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert_approx_eq!(
+            n.as_vector(),
+            Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)
+        );
+    }
+
+    #[test]
+    fn test_normal_on_transformed_sphere() {
+        let mut s = Sphere::new();
+        s.set_transform((&scaling(1.0, 0.5, 1.0) * &rotation_z(PI / 5.0)).unwrap())
+            .unwrap();
+        let k = CoordValue::sqrt(2.0) / 2.0;
+        let n = s.normal_at(&Point::new(0.0, k, -k));
+        assert_approx_eq!(n.as_vector(), Vector::new(0.0, 0.97014, -0.24254));
+    }
+
+    #[test]
+    fn test_bounds() {
+        let b = Sphere::new().local_bounds();
+        assert_eq!(b.min, Point::new(-1.0, -1.0, -1.0));
+        assert_eq!(b.max, Point::new(1.0, 1.0, 1.0));
+    }
+}
@@ -0,0 +1,118 @@
+use crate::bounds::Bounds;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersections, Ray};
+use crate::shapes::{Shape, ShapeProperties};
+use std::sync::Mutex;
+
+/// A shape with no surface, meant for testing the world/object space plumbing
+/// of [`Shape`]: it records the object-space ray it was intersected with and
+/// its normal at a point is that point taken as a vector.
+#[derive(Debug, Default)]
+pub struct TestShape {
+    properties: ShapeProperties,
+    saved_ray: Mutex<Option<Ray>>,
+}
+
+impl TestShape {
+    pub fn new() -> TestShape {
+        TestShape::default()
+    }
+
+    /// The last ray passed to [`Shape::local_intersect`], if any.
+    pub fn saved_ray(&self) -> Option<Ray> {
+        self.saved_ray.lock().unwrap().clone()
+    }
+}
+
+impl Shape for TestShape {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        *self.saved_ray.lock().unwrap() = Some(ray.clone());
+        Intersections::new()
+    }
+
+    fn local_normal_at(&self, point: &Point) -> Normal {
+        Normal::new(point.x(), point.y(), point.z())
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        Bounds::new(Point::new(-1.0, -1.0, -1.0), Point::new(1.0, 1.0, 1.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::transform::{rotation_z, scaling, translation};
+    use crate::tuple::consts::FRAC_1_SQRT_2;
+    use crate::tuple::CoordValue;
+    use crate::vector::Vector;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_no_ray_saved_initially() {
+        assert_eq!(TestShape::new().saved_ray(), None);
+    }
+
+    #[test]
+    fn test_intersect_scaled_shape_with_ray() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut s = TestShape::new();
+        s.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        let _ = s.intersect(&r);
+        let saved = s.saved_ray().unwrap();
+        assert_eq!(saved.origin, Point::new(0.0, 0.0, -2.5));
+        assert_eq!(saved.direction, Vector::new(0.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn test_intersect_translated_shape_with_ray() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut s = TestShape::new();
+        s.set_transform(translation(5.0, 0.0, 0.0)).unwrap();
+        let _ = s.intersect(&r);
+        let saved = s.saved_ray().unwrap();
+        assert_eq!(saved.origin, Point::new(-5.0, 0.0, -5.0));
+        assert_eq!(saved.direction, Vector::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_normal_on_translated_shape() {
+        let mut s = TestShape::new();
+        s.set_transform(translation(0.0, 1.0, 0.0)).unwrap();
+        let n = s.normal_at(&Point::new(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+        assert_approx_eq!(
+            n.as_vector(),
+            Vector::new(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2)
+        );
+    }
+
+    #[test]
+    fn test_normal_on_transformed_shape() {
+        let mut s = TestShape::new();
+        s.set_transform((&scaling(1.0, 0.5, 1.0) * &rotation_z(PI / 5.0)).unwrap())
+            .unwrap();
+        let k = CoordValue::sqrt(2.0) / 2.0;
+        let n = s.normal_at(&Point::new(0.0, k, -k));
+        assert_approx_eq!(n.as_vector(), Vector::new(0.0, 0.97014, -0.24254));
+    }
+
+    #[test]
+    fn test_parent_space_bounds() {
+        let mut s = TestShape::new();
+        s.set_transform((&translation(1.0, -3.0, 5.0) * &scaling(0.5, 2.0, 4.0)).unwrap())
+            .unwrap();
+        let b = s.parent_space_bounds();
+        assert_eq!(b.min, Point::new(0.5, -5.0, 1.0));
+        assert_eq!(b.max, Point::new(1.5, -1.0, 9.0));
+    }
+}
@@ -0,0 +1,230 @@
+use crate::approx::EPSILON;
+use crate::bounds::Bounds;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use crate::shapes::{Shape, ShapeProperties};
+use crate::tuple::CoordValue;
+use crate::vector::Vector;
+
+/// A flat triangle with corners `p1`, `p2` and `p3`, whose normal follows the
+/// right-hand rule from `p1` through `p2` to `p3`.
+#[derive(Debug, Clone)]
+pub struct Triangle {
+    properties: ShapeProperties,
+    p1: Point,
+    p2: Point,
+    p3: Point,
+    e1: Vector,
+    e2: Vector,
+    normal: Normal,
+}
+
+impl Triangle {
+    pub fn new(p1: Point, p2: Point, p3: Point) -> Triangle {
+        let e1 = &p2 - &p1;
+        let e2 = &p3 - &p1;
+        let normal = Normal::from_vector(&e2.cross(&e1));
+        Triangle {
+            properties: ShapeProperties::new(),
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal,
+        }
+    }
+
+    pub fn p1(&self) -> &Point {
+        &self.p1
+    }
+
+    pub fn p2(&self) -> &Point {
+        &self.p2
+    }
+
+    pub fn p3(&self) -> &Point {
+        &self.p3
+    }
+
+    /// Edge from `p1` to `p2`.
+    pub fn e1(&self) -> &Vector {
+        &self.e1
+    }
+
+    /// Edge from `p1` to `p3`.
+    pub fn e2(&self) -> &Vector {
+        &self.e2
+    }
+
+    pub fn normal(&self) -> &Normal {
+        &self.normal
+    }
+}
+
+/// Möller–Trumbore intersection of `ray` with the triangle at `p1` spanned by
+/// the edges `e1` and `e2`, returning `t` and the barycentric `u`/`v` of the
+/// hit (the weights of `p2` and `p3`).
+pub(crate) fn intersect_triangle(
+    ray: &Ray,
+    p1: &Point,
+    e1: &Vector,
+    e2: &Vector,
+) -> Option<(CoordValue, CoordValue, CoordValue)> {
+    let dir_cross_e2 = ray.direction.cross(e2);
+    let det = e1.dot(&dir_cross_e2);
+    // The ray is parallel to the triangle's plane. The tolerance is relative to
+    // the sizes the determinant is a product of, so that small triangles and
+    // short directions are not mistaken for parallel ones.
+    let scale = e1.magnitude() * e2.magnitude() * ray.direction.magnitude();
+    if det == 0.0 || det.abs() < EPSILON * scale {
+        return None;
+    }
+    let f = 1.0 / det;
+    let p1_to_origin = &ray.origin - p1;
+    let u = f * p1_to_origin.dot(&dir_cross_e2);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let origin_cross_e1 = p1_to_origin.cross(e1);
+    let v = f * ray.direction.dot(&origin_cross_e1);
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    Some((f * e2.dot(&origin_cross_e1), u, v))
+}
+
+impl Shape for Triangle {
+    fn properties(&self) -> &ShapeProperties {
+        &self.properties
+    }
+
+    fn properties_mut(&mut self) -> &mut ShapeProperties {
+        &mut self.properties
+    }
+
+    fn local_intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        match intersect_triangle(ray, &self.p1, &self.e1, &self.e2) {
+            Some((t, u, v)) => {
+                Intersections::from_vec(vec![Intersection::with_uv(t, self as &dyn Shape, u, v)])
+            }
+            None => Intersections::new(),
+        }
+    }
+
+    fn local_normal_at(&self, _point: &Point) -> Normal {
+        self.normal.clone()
+    }
+
+    fn local_bounds(&self) -> Bounds {
+        let mut bounds = Bounds::empty();
+        for point in [&self.p1, &self.p2, &self.p3] {
+            bounds.add_point(point);
+        }
+        bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+
+    fn triangle() -> Triangle {
+        Triangle::new(
+            Point::new(0.0, 1.0, 0.0),
+            Point::new(-1.0, 0.0, 0.0),
+            Point::new(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_new() {
+        let t = triangle();
+        assert_eq!(*t.p1(), Point::new(0.0, 1.0, 0.0));
+        assert_eq!(*t.p2(), Point::new(-1.0, 0.0, 0.0));
+        assert_eq!(*t.p3(), Point::new(1.0, 0.0, 0.0));
+        assert_eq!(*t.e1(), Vector::new(-1.0, -1.0, 0.0));
+        assert_eq!(*t.e2(), Vector::new(1.0, -1.0, 0.0));
+        assert_eq!(*t.normal(), Normal::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_normal_is_constant() {
+        let t = triangle();
+        for point in [
+            Point::new(0.0, 0.5, 0.0),
+            Point::new(-0.5, 0.75, 0.0),
+            Point::new(0.5, 0.25, 0.0),
+        ] {
+            assert_eq!(t.local_normal_at(&point), *t.normal());
+        }
+    }
+
+    #[test]
+    fn test_ray_parallel_to_triangle() {
+        let r = Ray::new(Point::new(0.0, -1.0, -2.0), Vector::new(0.0, 1.0, 0.0));
+        assert!(triangle().local_intersect(&r).is_empty());
+    }
+
+    #[test]
+    fn test_ray_misses_edges() {
+        for origin in [
+            // p1-p3 edge
+            Point::new(1.0, 1.0, -2.0),
+            // p1-p2 edge
+            Point::new(-1.0, 1.0, -2.0),
+            // p2-p3 edge
+            Point::new(0.0, -1.0, -2.0),
+        ] {
+            let r = Ray::new(origin, Vector::new(0.0, 0.0, 1.0));
+            assert!(triangle().local_intersect(&r).is_empty());
+        }
+    }
+
+    #[test]
+    fn test_ray_strikes_triangle() {
+        let t = triangle();
+        let r = Ray::new(Point::new(0.0, 0.5, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.local_intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+        assert!(std::ptr::addr_eq(xs[0].object, &t));
+    }
+
+    #[test]
+    fn test_ray_strikes_small_triangle() {
+        let t = Triangle::new(
+            Point::new(0.0, 0.005, 0.0),
+            Point::new(-0.005, 0.0, 0.0),
+            Point::new(0.005, 0.0, 0.0),
+        );
+        let r = Ray::new(Point::new(0.0, 0.002, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = t.local_intersect(&r);
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+    }
+
+    #[test]
+    fn test_intersection_stores_uv() {
+        let r = Ray::new(Point::new(-0.2, 0.3, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let t = triangle();
+        let xs = t.local_intersect(&r);
+        let (u, v) = xs[0].uv.unwrap();
+        assert_approx_eq!(u, 0.45);
+        assert_approx_eq!(v, 0.25);
+    }
+
+    #[test]
+    fn test_bounds() {
+        let t = Triangle::new(
+            Point::new(-3.0, 7.0, 2.0),
+            Point::new(6.0, 2.0, -4.0),
+            Point::new(2.0, -1.0, -1.0),
+        );
+        let b = t.local_bounds();
+        assert_eq!(b.min, Point::new(-3.0, -1.0, -4.0));
+        assert_eq!(b.max, Point::new(6.0, 7.0, 2.0));
+    }
+}
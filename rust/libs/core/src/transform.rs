@@ -0,0 +1,557 @@
+use crate::fixed_matrix::Matrix4;
+use crate::matrix::{Matrix, MatrixError};
+use crate::point::Point;
+use crate::tuple::widen;
+use crate::vector::Vector;
+
+pub fn translation(x: f64, y: f64, z: f64) -> Matrix {
+    Matrix::from(Matrix4::translation(x, y, z))
+}
+
+pub fn scaling(x: f64, y: f64, z: f64) -> Matrix {
+    Matrix::from(Matrix4::scaling(x, y, z))
+}
+
+pub fn rotation_x(angle: f64) -> Matrix {
+    Matrix::from(Matrix4::rotation_x(angle))
+}
+
+pub fn rotation_y(angle: f64) -> Matrix {
+    Matrix::from(Matrix4::rotation_y(angle))
+}
+
+pub fn rotation_z(angle: f64) -> Matrix {
+    Matrix::from(Matrix4::rotation_z(angle))
+}
+
+/// Rotation around an arbitrary axis through the origin (Rodrigues' formula).
+/// The axis does not need to be normalized.
+pub fn rotation_axis_angle(axis: &Vector, angle: f64) -> Matrix {
+    Matrix::from(Matrix4::rotation_axis_angle(axis, angle))
+}
+
+pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix {
+    Matrix::from(Matrix4::shearing(xy, xz, yx, yz, zx, zy))
+}
+
+/// Camera-from-world transformation for an eye at `from` looking at `to`, with
+/// `up` roughly pointing upwards.
+///
+/// The result is meaningless (it contains NaN) when `from` equals `to` or `up` is
+/// parallel to the viewing direction; [`view_transform_inverse`] reports those.
+pub fn view_transform(from: &Point, to: &Point, up: &Vector) -> Matrix {
+    let forward = Vector::from(to.as_tuple().sub(&from.as_tuple())).normalize();
+    let left = forward.cross(&up.normalize());
+    let true_up = left.cross(&forward);
+    let row = |v: &Vector, sign: f64| {
+        [
+            sign * widen(v.x()),
+            sign * widen(v.y()),
+            sign * widen(v.z()),
+            0.0,
+        ]
+    };
+    let orientation = Matrix4::from_array([
+        row(&left, 1.0),
+        row(&true_up, 1.0),
+        row(&forward, -1.0),
+        [0.0, 0.0, 0.0, 1.0],
+    ]);
+    Matrix::from(
+        Matrix4::translation(-widen(from.x()), -widen(from.y()), -widen(from.z()))
+            .then(orientation),
+    )
+}
+
+/// World-from-camera transformation, the inverse of [`view_transform`].
+///
+/// Fails when `from` equals `to` or `up` is parallel to the viewing direction,
+/// as the orientation is then degenerate.
+pub fn view_transform_inverse(
+    from: &Point,
+    to: &Point,
+    up: &Vector,
+) -> Result<Matrix, MatrixError> {
+    let direction = Vector::from(to.as_tuple().sub(&from.as_tuple()));
+    if is_degenerate(&direction) || is_degenerate(&direction.cross(up)) {
+        return Err(MatrixError::NotInvertible);
+    }
+    view_transform(from, to, up).inverse()
+}
+
+fn is_degenerate(v: &Vector) -> bool {
+    let length = v.magnitude();
+    !length.is_finite() || length == 0.0
+}
+
+/// Fixed-size versions of the transformations above.
+impl Matrix4 {
+    pub fn translation(x: f64, y: f64, z: f64) -> Matrix4 {
+        Matrix4::from_array([
+            [1.0, 0.0, 0.0, x],
+            [0.0, 1.0, 0.0, y],
+            [0.0, 0.0, 1.0, z],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn scaling(x: f64, y: f64, z: f64) -> Matrix4 {
+        Matrix4::from_array([
+            [x, 0.0, 0.0, 0.0],
+            [0.0, y, 0.0, 0.0],
+            [0.0, 0.0, z, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_x(angle: f64) -> Matrix4 {
+        let (sin, cos) = angle.sin_cos();
+        Matrix4::from_array([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, cos, -sin, 0.0],
+            [0.0, sin, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_y(angle: f64) -> Matrix4 {
+        let (sin, cos) = angle.sin_cos();
+        Matrix4::from_array([
+            [cos, 0.0, sin, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [-sin, 0.0, cos, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_z(angle: f64) -> Matrix4 {
+        let (sin, cos) = angle.sin_cos();
+        Matrix4::from_array([
+            [cos, -sin, 0.0, 0.0],
+            [sin, cos, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn rotation_axis_angle(axis: &Vector, angle: f64) -> Matrix4 {
+        let axis = axis.normalize();
+        let (x, y, z) = (widen(axis.x()), widen(axis.y()), widen(axis.z()));
+        let (sin, cos) = angle.sin_cos();
+        let t = 1.0 - cos;
+        Matrix4::from_array([
+            [
+                t * x * x + cos,
+                t * x * y - sin * z,
+                t * x * z + sin * y,
+                0.0,
+            ],
+            [
+                t * x * y + sin * z,
+                t * y * y + cos,
+                t * y * z - sin * x,
+                0.0,
+            ],
+            [
+                t * x * z - sin * y,
+                t * y * z + sin * x,
+                t * z * z + cos,
+                0.0,
+            ],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    pub fn shearing(xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix4 {
+        Matrix4::from_array([
+            [1.0, xy, xz, 0.0],
+            [yx, 1.0, yz, 0.0],
+            [zx, zy, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+}
+
+/// Fluent composition of transformations.
+///
+/// Each call applies its transformation after the ones already in the chain, so
+/// `Matrix4::identity().rotate_x(a).scale(2.0, 2.0, 2.0).translate(1.0, 0.0, 0.0)`
+/// rotates first and translates last.
+impl Matrix4 {
+    pub fn then(self, transform: Matrix4) -> Matrix4 {
+        transform * self
+    }
+
+    pub fn translate(self, x: f64, y: f64, z: f64) -> Matrix4 {
+        self.then(Matrix4::translation(x, y, z))
+    }
+
+    pub fn scale(self, x: f64, y: f64, z: f64) -> Matrix4 {
+        self.then(Matrix4::scaling(x, y, z))
+    }
+
+    pub fn rotate_x(self, angle: f64) -> Matrix4 {
+        self.then(Matrix4::rotation_x(angle))
+    }
+
+    pub fn rotate_y(self, angle: f64) -> Matrix4 {
+        self.then(Matrix4::rotation_y(angle))
+    }
+
+    pub fn rotate_z(self, angle: f64) -> Matrix4 {
+        self.then(Matrix4::rotation_z(angle))
+    }
+
+    pub fn rotate_axis_angle(self, axis: &Vector, angle: f64) -> Matrix4 {
+        self.then(Matrix4::rotation_axis_angle(axis, angle))
+    }
+
+    pub fn shear(self, xy: f64, xz: f64, yx: f64, yz: f64, zx: f64, zy: f64) -> Matrix4 {
+        self.then(Matrix4::shearing(xy, xz, yx, yz, zx, zy))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_approx_eq;
+    use crate::tuple::CoordValue;
+    use std::f64::consts::PI;
+
+    #[test]
+    fn test_translation() {
+        let t = translation(5.0, -3.0, 2.0);
+        assert_eq!(t.get_column(3), [5.0, -3.0, 2.0, 1.0]);
+        assert_eq!(t.submatrix(3, 3), Matrix::identity(3));
+    }
+
+    #[test]
+    fn test_inverse_of_translation() {
+        let t = translation(5.0, -3.0, 2.0);
+        assert_eq!(t.inverse().unwrap(), translation(-5.0, 3.0, -2.0));
+    }
+
+    #[test]
+    fn test_scaling() {
+        let s = scaling(2.0, 3.0, 4.0);
+        assert_eq!(s.get_diagonal(), [2.0, 3.0, 4.0, 1.0]);
+    }
+
+    #[test]
+    fn test_inverse_of_scaling() {
+        let s = scaling(2.0, 4.0, 5.0);
+        assert_eq!(s.inverse().unwrap(), scaling(0.5, 0.25, 0.2));
+    }
+
+    #[test]
+    fn test_rotation_x() {
+        let r = rotation_x(PI / 2.0);
+        assert_approx_eq!(
+            r,
+            Matrix::from_string("1 0 0 0\n0 0 -1 0\n0 1 0 0\n0 0 0 1")
+        );
+    }
+
+    #[test]
+    fn test_inverse_of_rotation_x() {
+        let r = rotation_x(PI / 4.0);
+        assert_approx_eq!(r.inverse().unwrap(), rotation_x(-PI / 4.0));
+    }
+
+    #[test]
+    fn test_rotation_y() {
+        let r = rotation_y(PI / 2.0);
+        assert_approx_eq!(
+            r,
+            Matrix::from_string("0 0 1 0\n0 1 0 0\n-1 0 0 0\n0 0 0 1")
+        );
+    }
+
+    #[test]
+    fn test_rotation_z() {
+        let r = rotation_z(PI / 2.0);
+        assert_approx_eq!(
+            r,
+            Matrix::from_string("0 -1 0 0\n1 0 0 0\n0 0 1 0\n0 0 0 1")
+        );
+    }
+
+    #[test]
+    fn test_rotation_axis_angle_matches_axis_rotations() {
+        let angle = PI / 3.0;
+        assert_approx_eq!(
+            rotation_axis_angle(&Vector::new(1.0, 0.0, 0.0), angle),
+            rotation_x(angle)
+        );
+        assert_approx_eq!(
+            rotation_axis_angle(&Vector::new(0.0, 2.0, 0.0), angle),
+            rotation_y(angle)
+        );
+        assert_approx_eq!(
+            rotation_axis_angle(&Vector::new(0.0, 0.0, 0.5), angle),
+            rotation_z(angle)
+        );
+    }
+
+    #[test]
+    fn test_rotation_axis_angle_around_diagonal() {
+        // A third of a turn around (1, 1, 1) cycles the axes x -> y -> z -> x
+        let r = rotation_axis_angle(&Vector::new(1.0, 1.0, 1.0), 2.0 * PI / 3.0);
+        assert_approx_eq!(r, Matrix::from_string("0 0 1 0\n1 0 0 0\n0 1 0 0\n0 0 0 1"));
+    }
+
+    #[test]
+    fn test_shearing() {
+        let s = shearing(1.0, 2.0, 3.0, 4.0, 5.0, 6.0);
+        assert_eq!(s, Matrix::from_string("1 1 2 0\n3 1 4 0\n5 6 1 0\n0 0 0 1"));
+    }
+
+    #[test]
+    fn test_fluent_builder_applies_in_order() {
+        let t = Matrix4::identity()
+            .rotate_x(PI / 2.0)
+            .scale(5.0, 5.0, 5.0)
+            .translate(10.0, 5.0, 7.0);
+        let expected = (&translation(10.0, 5.0, 7.0)
+            * &(&scaling(5.0, 5.0, 5.0) * &rotation_x(PI / 2.0)).unwrap())
+            .unwrap();
+        assert_approx_eq!(Matrix::from(t), expected);
+    }
+
+    #[test]
+    fn test_fluent_builder_with_every_transformation() {
+        let axis = Vector::new(1.0, 1.0, 0.0);
+        let t = Matrix4::identity()
+            .rotate_y(PI / 4.0)
+            .rotate_z(PI / 6.0)
+            .rotate_axis_angle(&axis, PI / 5.0)
+            .shear(1.0, 0.0, 0.0, 0.0, 0.0, 0.0);
+        let mut expected = rotation_y(PI / 4.0);
+        for m in [
+            rotation_z(PI / 6.0),
+            rotation_axis_angle(&axis, PI / 5.0),
+            shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+        ] {
+            expected = (m * expected).unwrap();
+        }
+        assert_approx_eq!(Matrix::from(t), expected);
+    }
+
+    #[test]
+    fn test_fixed_size_transformations_match() {
+        let axis = Vector::new(0.0, 1.0, 1.0);
+        let cases = [
+            (
+                Matrix4::translation(1.0, 2.0, 3.0),
+                translation(1.0, 2.0, 3.0),
+            ),
+            (Matrix4::scaling(1.0, 2.0, 3.0), scaling(1.0, 2.0, 3.0)),
+            (Matrix4::rotation_x(PI / 3.0), rotation_x(PI / 3.0)),
+            (Matrix4::rotation_y(PI / 3.0), rotation_y(PI / 3.0)),
+            (Matrix4::rotation_z(PI / 3.0), rotation_z(PI / 3.0)),
+            (
+                Matrix4::rotation_axis_angle(&axis, PI / 3.0),
+                rotation_axis_angle(&axis, PI / 3.0),
+            ),
+            (
+                Matrix4::shearing(1.0, 2.0, 3.0, 4.0, 5.0, 6.0),
+                shearing(1.0, 2.0, 3.0, 4.0, 5.0, 6.0),
+            ),
+        ];
+        for (fixed, dynamic) in cases {
+            assert_eq!(Matrix::from(fixed), dynamic);
+        }
+    }
+
+    #[test]
+    fn test_translate_point() {
+        let t = translation(5.0, -3.0, 2.0);
+        let p = Point::new(-3.0, 4.0, 5.0);
+        assert_eq!((&t * &p).unwrap(), Point::new(2.0, 1.0, 7.0));
+    }
+
+    #[test]
+    fn test_inverse_translation_moves_point_back() {
+        let inv = translation(5.0, -3.0, 2.0).inverse().unwrap();
+        let p = Point::new(-3.0, 4.0, 5.0);
+        assert_eq!((&inv * &p).unwrap(), Point::new(-8.0, 7.0, 3.0));
+    }
+
+    #[test]
+    fn test_translation_does_not_affect_vectors() {
+        let t = translation(5.0, -3.0, 2.0);
+        let v = Vector::new(-3.0, 4.0, 5.0);
+        assert_eq!((&t * &v).unwrap(), v);
+    }
+
+    #[test]
+    fn test_scale_point_and_vector() {
+        let s = scaling(2.0, 3.0, 4.0);
+        assert_eq!(
+            (&s * &Point::new(-4.0, 6.0, 8.0)).unwrap(),
+            Point::new(-8.0, 18.0, 32.0)
+        );
+        assert_eq!(
+            (&s * &Vector::new(-4.0, 6.0, 8.0)).unwrap(),
+            Vector::new(-8.0, 18.0, 32.0)
+        );
+    }
+
+    #[test]
+    fn test_reflection_is_scaling_by_negative_value() {
+        let s = scaling(-1.0, 1.0, 1.0);
+        assert_eq!(
+            (&s * &Point::new(2.0, 3.0, 4.0)).unwrap(),
+            Point::new(-2.0, 3.0, 4.0)
+        );
+    }
+
+    #[test]
+    fn test_rotate_point_around_x() {
+        let p = Point::new(0.0, 1.0, 0.0);
+        let half_quarter = rotation_x(PI / 4.0);
+        let full_quarter = rotation_x(PI / 2.0);
+        let h = CoordValue::sqrt(2.0) / 2.0;
+        assert_approx_eq!((&half_quarter * &p).unwrap(), Point::new(0.0, h, h));
+        assert_approx_eq!((&full_quarter * &p).unwrap(), Point::new(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn test_rotate_point_around_y_and_z() {
+        let h = CoordValue::sqrt(2.0) / 2.0;
+        assert_approx_eq!(
+            (&rotation_y(PI / 4.0) * &Point::new(0.0, 0.0, 1.0)).unwrap(),
+            Point::new(h, 0.0, h)
+        );
+        assert_approx_eq!(
+            (&rotation_z(PI / 4.0) * &Point::new(0.0, 1.0, 0.0)).unwrap(),
+            Point::new(-h, h, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_shearing_moves_each_component_in_proportion_to_others() {
+        let p = Point::new(2.0, 3.0, 4.0);
+        let cases = [
+            (
+                shearing(1.0, 0.0, 0.0, 0.0, 0.0, 0.0),
+                Point::new(5.0, 3.0, 4.0),
+            ),
+            (
+                shearing(0.0, 1.0, 0.0, 0.0, 0.0, 0.0),
+                Point::new(6.0, 3.0, 4.0),
+            ),
+            (
+                shearing(0.0, 0.0, 1.0, 0.0, 0.0, 0.0),
+                Point::new(2.0, 5.0, 4.0),
+            ),
+            (
+                shearing(0.0, 0.0, 0.0, 1.0, 0.0, 0.0),
+                Point::new(2.0, 7.0, 4.0),
+            ),
+            (
+                shearing(0.0, 0.0, 0.0, 0.0, 1.0, 0.0),
+                Point::new(2.0, 3.0, 6.0),
+            ),
+            (
+                shearing(0.0, 0.0, 0.0, 0.0, 0.0, 1.0),
+                Point::new(2.0, 3.0, 7.0),
+            ),
+        ];
+        for (transform, expected) in cases {
+            assert_eq!((&transform * &p).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_chained_transformations_apply_in_order() {
+        let p = Point::new(1.0, 0.0, 1.0);
+        let t = Matrix4::identity()
+            .rotate_x(PI / 2.0)
+            .scale(5.0, 5.0, 5.0)
+            .translate(10.0, 5.0, 7.0);
+        assert_approx_eq!(&t * &p, Point::new(15.0, 0.0, 7.0));
+    }
+
+    #[test]
+    fn test_view_transform_for_default_orientation() {
+        let from = Point::new(0.0, 0.0, 0.0);
+        let to = Point::new(0.0, 0.0, -1.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        assert_eq!(view_transform(&from, &to, &up), Matrix::identity(4));
+    }
+
+    #[test]
+    fn test_view_transform_looking_in_positive_z() {
+        let from = Point::new(0.0, 0.0, 0.0);
+        let to = Point::new(0.0, 0.0, 1.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        assert_eq!(view_transform(&from, &to, &up), scaling(-1.0, 1.0, -1.0));
+    }
+
+    #[test]
+    fn test_view_transform_moves_the_world() {
+        let from = Point::new(0.0, 0.0, 8.0);
+        let to = Point::new(0.0, 0.0, 0.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        assert_eq!(view_transform(&from, &to, &up), translation(0.0, 0.0, -8.0));
+    }
+
+    #[test]
+    fn test_arbitrary_view_transform() {
+        let from = Point::new(1.0, 3.0, 2.0);
+        let to = Point::new(4.0, -2.0, 8.0);
+        let up = Vector::new(1.0, 1.0, 0.0);
+        let t = view_transform(&from, &to, &up);
+        let expected = Matrix::from_string(
+            "-0.50709 0.50709 0.67612 -2.36643\n\
+             0.76772 0.60609 0.12122 -2.82843\n\
+             -0.35857 0.59761 -0.71714 0\n\
+             0 0 0 1",
+        );
+        assert_approx_eq!(t, expected);
+    }
+
+    #[test]
+    fn test_view_transform_inverse() {
+        let from = Point::new(1.0, 3.0, 2.0);
+        let to = Point::new(4.0, -2.0, 8.0);
+        let up = Vector::new(1.0, 1.0, 0.0);
+        let inverse = view_transform_inverse(&from, &to, &up).unwrap();
+        let product = (&inverse * &view_transform(&from, &to, &up)).unwrap();
+        assert_approx_eq!(product, Matrix::identity(4));
+        assert_approx_eq!((&inverse * &Point::new(0.0, 0.0, 0.0)).unwrap(), from);
+    }
+
+    #[test]
+    fn test_view_transform_inverse_with_degenerate_up() {
+        let from = Point::new(0.0, 0.0, 0.0);
+        let to = Point::new(0.0, 1.0, 0.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        assert_eq!(
+            view_transform_inverse(&from, &to, &up),
+            Err(MatrixError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn test_view_transform_inverse_with_eye_at_target() {
+        let from = Point::new(1.0, 2.0, 3.0);
+        let up = Vector::new(0.0, 1.0, 0.0);
+        assert_eq!(
+            view_transform_inverse(&from, &from, &up),
+            Err(MatrixError::NotInvertible)
+        );
+    }
+
+    #[test]
+    fn test_view_transform_inverse_with_zero_up() {
+        let from = Point::new(0.0, 0.0, 0.0);
+        let to = Point::new(0.0, 0.0, -1.0);
+        let up = Vector::new(0.0, 0.0, 0.0);
+        assert_eq!(
+            view_transform_inverse(&from, &to, &up),
+            Err(MatrixError::NotInvertible)
+        );
+    }
+}
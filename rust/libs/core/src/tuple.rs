@@ -1,15 +1,146 @@
+use std::ops;
+
+#[cfg(not(feature = "f64"))]
 pub type CoordValue = f32;
+#[cfg(feature = "f64")]
+pub type CoordValue = f64;
+
+#[cfg(not(feature = "f64"))]
+pub use std::f32::consts;
+#[cfg(feature = "f64")]
+pub use std::f64::consts;
+
+/// Widens a coordinate to the `f64` that matrices always compute in.
+#[allow(clippy::unnecessary_cast)] // a no-op with the `f64` feature
+pub(crate) fn widen(value: CoordValue) -> f64 {
+    value as f64
+}
+
+// The operator traits are deliberately not imported: with them in scope, calls
+// such as `a.add(&b)` on an owned `a` would resolve to `ops::Add::add` instead
+// of the inherent methods and move `a`.
+
+/// Given an `impl Op<&Rhs> for &Lhs`, derives the by-value and mixed variants of
+/// the operator plus its compound assignment.
+macro_rules! forward_binop {
+    ($lhs:ty, $op:ident, $method:ident, $assign_op:ident, $assign_method:ident, $rhs:ty, $output:ty) => {
+        impl std::ops::$op<$rhs> for $lhs {
+            type Output = $output;
+
+            fn $method(self, other: $rhs) -> $output {
+                std::ops::$op::$method(&self, &other)
+            }
+        }
+
+        impl std::ops::$op<&$rhs> for $lhs {
+            type Output = $output;
+
+            fn $method(self, other: &$rhs) -> $output {
+                std::ops::$op::$method(&self, other)
+            }
+        }
+
+        impl std::ops::$op<$rhs> for &$lhs {
+            type Output = $output;
+
+            fn $method(self, other: $rhs) -> $output {
+                std::ops::$op::$method(self, &other)
+            }
+        }
+
+        impl std::ops::$assign_op<$rhs> for $lhs {
+            fn $assign_method(&mut self, other: $rhs) {
+                *self = std::ops::$op::$method(&*self, &other);
+            }
+        }
+
+        impl std::ops::$assign_op<&$rhs> for $lhs {
+            fn $assign_method(&mut self, other: &$rhs) {
+                *self = std::ops::$op::$method(&*self, other);
+            }
+        }
+    };
+}
+
+/// Given an `impl Op<CoordValue> for &Lhs`, derives the by-value variant of the
+/// operator plus its compound assignment.
+macro_rules! forward_scalar_op {
+    ($lhs:ty, $op:ident, $method:ident, $assign_op:ident, $assign_method:ident) => {
+        impl std::ops::$op<CoordValue> for $lhs {
+            type Output = $lhs;
+
+            fn $method(self, scalar: CoordValue) -> $lhs {
+                std::ops::$op::$method(&self, scalar)
+            }
+        }
+
+        impl std::ops::$assign_op<CoordValue> for $lhs {
+            fn $assign_method(&mut self, scalar: CoordValue) {
+                *self = std::ops::$op::$method(&*self, scalar);
+            }
+        }
+    };
+}
+
+/// Given an `impl Neg for &T`, derives the by-value variant.
+macro_rules! forward_neg {
+    ($t:ty) => {
+        impl std::ops::Neg for $t {
+            type Output = $t;
+
+            fn neg(self) -> $t {
+                -&self
+            }
+        }
+    };
+}
+
+pub(crate) use {forward_binop, forward_neg, forward_scalar_op};
 
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tuple {
     pub x: CoordValue,
     pub y: CoordValue,
     pub z: CoordValue,
+    pub w: CoordValue,
+}
+
+/// Serialized form of points and vectors, whose `w` is implied by their type.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+pub(crate) struct Coordinates {
+    x: CoordValue,
+    y: CoordValue,
+    z: CoordValue,
+}
+
+#[cfg(feature = "serde")]
+impl Coordinates {
+    pub(crate) fn new(x: CoordValue, y: CoordValue, z: CoordValue) -> Coordinates {
+        Coordinates { x, y, z }
+    }
+
+    pub(crate) fn as_array(&self) -> [CoordValue; 3] {
+        [self.x, self.y, self.z]
+    }
 }
 
 impl Tuple {
     pub fn new(x: CoordValue, y: CoordValue, z: CoordValue) -> Tuple {
-        Tuple { x, y, z }
+        Tuple { x, y, z, w: 0.0 }
+    }
+
+    pub fn homogeneous(x: CoordValue, y: CoordValue, z: CoordValue, w: CoordValue) -> Tuple {
+        Tuple { x, y, z, w }
+    }
+
+    pub fn point(x: CoordValue, y: CoordValue, z: CoordValue) -> Tuple {
+        Tuple { x, y, z, w: 1.0 }
+    }
+
+    pub fn vector(x: CoordValue, y: CoordValue, z: CoordValue) -> Tuple {
+        Tuple { x, y, z, w: 0.0 }
     }
 
     pub fn from_array(a: [CoordValue; 3]) -> Tuple {
@@ -17,6 +148,16 @@ impl Tuple {
             x: a[0],
             y: a[1],
             z: a[2],
+            w: 0.0,
+        }
+    }
+
+    pub fn from_homogeneous_array(a: [CoordValue; 4]) -> Tuple {
+        Tuple {
+            x: a[0],
+            y: a[1],
+            z: a[2],
+            w: a[3],
         }
     }
 
@@ -25,9 +166,14 @@ impl Tuple {
             x: t.0,
             y: t.1,
             z: t.2,
+            w: 0.0,
         }
     }
 
+    pub fn as_homogeneous_array(&self) -> [CoordValue; 4] {
+        [self.x, self.y, self.z, self.w]
+    }
+
     pub fn x(&self) -> CoordValue {
         self.x
     }
@@ -40,11 +186,24 @@ impl Tuple {
         self.z
     }
 
+    pub fn w(&self) -> CoordValue {
+        self.w
+    }
+
+    pub fn is_point(&self) -> bool {
+        self.w == 1.0
+    }
+
+    pub fn is_vector(&self) -> bool {
+        self.w == 0.0
+    }
+
     pub fn add(&self, other: &Tuple) -> Tuple {
         Tuple {
             x: self.x + other.x,
             y: self.y + other.y,
             z: self.z + other.z,
+            w: self.w + other.w,
         }
     }
 
@@ -53,6 +212,7 @@ impl Tuple {
             x: self.x - other.x,
             y: self.y - other.y,
             z: self.z - other.z,
+            w: self.w - other.w,
         }
     }
 
@@ -61,6 +221,7 @@ impl Tuple {
             x: -self.x,
             y: -self.y,
             z: -self.z,
+            w: -self.w,
         }
     }
 
@@ -69,6 +230,7 @@ impl Tuple {
             x: self.x * scalar,
             y: self.y * scalar,
             z: self.z * scalar,
+            w: self.w * scalar,
         }
     }
 
@@ -77,6 +239,7 @@ impl Tuple {
             x: self.x / scalar,
             y: self.y / scalar,
             z: self.z / scalar,
+            w: self.w / scalar,
         }
     }
 
@@ -87,10 +250,56 @@ impl Tuple {
 
 impl PartialEq for Tuple {
     fn eq(&self, other: &Tuple) -> bool {
-        self.x == other.x && self.y == other.y && self.z == other.z
+        self.x == other.x && self.y == other.y && self.z == other.z && self.w == other.w
+    }
+}
+
+impl ops::Add<&Tuple> for &Tuple {
+    type Output = Tuple;
+
+    fn add(self, other: &Tuple) -> Tuple {
+        Tuple::add(self, other)
     }
 }
 
+impl ops::Sub<&Tuple> for &Tuple {
+    type Output = Tuple;
+
+    fn sub(self, other: &Tuple) -> Tuple {
+        Tuple::sub(self, other)
+    }
+}
+
+impl ops::Neg for &Tuple {
+    type Output = Tuple;
+
+    fn neg(self) -> Tuple {
+        Tuple::neg(self)
+    }
+}
+
+impl ops::Mul<CoordValue> for &Tuple {
+    type Output = Tuple;
+
+    fn mul(self, scalar: CoordValue) -> Tuple {
+        self.scalar_mul(scalar)
+    }
+}
+
+impl ops::Div<CoordValue> for &Tuple {
+    type Output = Tuple;
+
+    fn div(self, scalar: CoordValue) -> Tuple {
+        self.scalar_div(scalar)
+    }
+}
+
+forward_binop!(Tuple, Add, add, AddAssign, add_assign, Tuple, Tuple);
+forward_binop!(Tuple, Sub, sub, SubAssign, sub_assign, Tuple, Tuple);
+forward_neg!(Tuple);
+forward_scalar_op!(Tuple, Mul, mul, MulAssign, mul_assign);
+forward_scalar_op!(Tuple, Div, div, DivAssign, div_assign);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -101,10 +310,12 @@ mod tests {
             x: 1.0,
             y: 2.0,
             z: 3.0,
+            w: 0.0,
         };
         assert_eq!(p.x, 1.0);
         assert_eq!(p.y, 2.0);
         assert_eq!(p.z, 3.0);
+        assert_eq!(p.w, 0.0);
     }
 
     #[test]
@@ -193,4 +404,87 @@ mod tests {
         assert_eq!(p2.y, -1.0);
         assert_eq!(p2.z, 1.5);
     }
+
+    #[test]
+    fn it_should_create_points_with_w_1() {
+        let p = Tuple::point(4.0, -4.0, 3.0);
+        assert_eq!(p.w, 1.0);
+        assert!(p.is_point());
+        assert!(!p.is_vector());
+    }
+
+    #[test]
+    fn it_should_create_vectors_with_w_0() {
+        let v = Tuple::vector(4.0, -4.0, 3.0);
+        assert_eq!(v.w, 0.0);
+        assert!(v.is_vector());
+        assert!(!v.is_point());
+    }
+
+    #[test]
+    fn it_should_create_homogeneous_tuples() {
+        let t = Tuple::homogeneous(4.3, -4.2, 3.1, 1.0);
+        assert_eq!(t.as_homogeneous_array(), [4.3, -4.2, 3.1, 1.0]);
+        assert_eq!(Tuple::from_homogeneous_array([4.3, -4.2, 3.1, 1.0]), t);
+    }
+
+    #[test]
+    fn it_should_not_equal_with_different_w() {
+        let p = Tuple::point(1.0, 2.0, 3.0);
+        let v = Tuple::vector(1.0, 2.0, 3.0);
+        assert!(!p.eq(&v));
+    }
+
+    #[test]
+    fn it_should_keep_w_consistent_in_arithmetic() {
+        let p = Tuple::point(3.0, 2.0, 1.0);
+        let v = Tuple::vector(5.0, 6.0, 7.0);
+        assert!(p.add(&v).is_point());
+        assert!(p.sub(&Tuple::point(5.0, 6.0, 7.0)).is_vector());
+        assert!(p.sub(&v).is_point());
+        assert!(v.sub(&v).is_vector());
+    }
+
+    #[test]
+    fn it_should_support_operators() {
+        let t1 = Tuple::from_array([3.0, -2.0, 5.0]);
+        let t2 = Tuple::from_array([-2.0, 3.0, 1.0]);
+        assert_eq!(&t1 + &t2, Tuple::new(1.0, 1.0, 6.0));
+        assert_eq!(&t1 - &t2, Tuple::new(5.0, -5.0, 4.0));
+        assert_eq!(-&t1, Tuple::new(-3.0, 2.0, -5.0));
+        assert_eq!(&t1 * 2.0, Tuple::new(6.0, -4.0, 10.0));
+        assert_eq!(&t1 / 2.0, Tuple::new(1.5, -1.0, 2.5));
+        assert_eq!(t1.clone() + t2.clone(), Tuple::new(1.0, 1.0, 6.0));
+        assert_eq!(t1.clone() + &t2, Tuple::new(1.0, 1.0, 6.0));
+        assert_eq!(&t1 + t2.clone(), Tuple::new(1.0, 1.0, 6.0));
+        assert_eq!(-t1, Tuple::new(-3.0, 2.0, -5.0));
+    }
+
+    #[test]
+    fn it_should_support_assign_operators() {
+        let mut t = Tuple::from_array([1.0, 2.0, 3.0]);
+        t += Tuple::from_array([1.0, 1.0, 1.0]);
+        assert_eq!(t, Tuple::new(2.0, 3.0, 4.0));
+        t -= &Tuple::from_array([2.0, 2.0, 2.0]);
+        assert_eq!(t, Tuple::new(0.0, 1.0, 2.0));
+        t *= 4.0;
+        assert_eq!(t, Tuple::new(0.0, 4.0, 8.0));
+        t /= 2.0;
+        assert_eq!(t, Tuple::new(0.0, 2.0, 4.0));
+    }
+
+    #[test]
+    fn it_should_use_the_selected_precision() {
+        let expected = if cfg!(feature = "f64") { 8 } else { 4 };
+        assert_eq!(std::mem::size_of::<CoordValue>(), expected);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let t = Tuple::homogeneous(1.0, -2.5, 3.0, 1.0);
+        let json = serde_json::to_string(&t).unwrap();
+        assert_eq!(json, r#"{"x":1.0,"y":-2.5,"z":3.0,"w":1.0}"#);
+        assert_eq!(serde_json::from_str::<Tuple>(&json).unwrap(), t);
+    }
 }
@@ -1,6 +1,12 @@
-use crate::tuple::{CoordValue, Tuple};
+use crate::tuple::{forward_binop, forward_neg, forward_scalar_op, CoordValue, Tuple};
+use std::ops;
 
 #[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "crate::tuple::Coordinates", into = "crate::tuple::Coordinates")
+)]
 pub struct Vector {
     tuple: Tuple,
 }
@@ -8,24 +14,21 @@ pub struct Vector {
 impl Vector {
     pub fn new(x: CoordValue, y: CoordValue, z: CoordValue) -> Vector {
         Vector {
-            tuple: Tuple::new(x, y, z),
+            tuple: Tuple::vector(x, y, z),
         }
     }
 
+    /// Builds a vector from a tuple, discarding its `w` component.
     pub fn from(t: Tuple) -> Vector {
-        Vector { tuple: t }
+        Vector::new(t.x, t.y, t.z)
     }
 
     pub fn from_array(a: [CoordValue; 3]) -> Vector {
-        Vector {
-            tuple: Tuple::from_array(a),
-        }
+        Vector::new(a[0], a[1], a[2])
     }
 
     pub fn from_tuple(t: (CoordValue, CoordValue, CoordValue)) -> Vector {
-        Vector {
-            tuple: Tuple::from_tuple(t),
-        }
+        Vector::new(t.0, t.1, t.2)
     }
 
     pub fn from_vector(v: &Vector) -> Vector {
@@ -73,20 +76,14 @@ impl Vector {
     }
 
     pub fn magnitude(&self) -> CoordValue {
-        f32::sqrt(
+        CoordValue::sqrt(
             self.tuple.x * self.tuple.x + self.tuple.y * self.tuple.y + self.tuple.z * self.tuple.z,
         )
     }
 
     pub fn normalize(&self) -> Vector {
         let mag = self.magnitude();
-        Vector {
-            tuple: Tuple {
-                x: self.tuple.x / mag,
-                y: self.tuple.y / mag,
-                z: self.tuple.z / mag,
-            },
-        }
+        Vector::new(self.tuple.x / mag, self.tuple.y / mag, self.tuple.z / mag)
     }
 
     pub fn dot(&self, other: &Vector) -> CoordValue {
@@ -94,13 +91,32 @@ impl Vector {
     }
 
     pub fn cross(&self, other: &Vector) -> Vector {
-        Vector {
-            tuple: Tuple {
-                x: self.tuple.y * other.tuple.z - self.tuple.z * other.tuple.y,
-                y: self.tuple.z * other.tuple.x - self.tuple.x * other.tuple.z,
-                z: self.tuple.x * other.tuple.y - self.tuple.y * other.tuple.x,
-            },
+        Vector::new(
+            self.tuple.y * other.tuple.z - self.tuple.z * other.tuple.y,
+            self.tuple.z * other.tuple.x - self.tuple.x * other.tuple.z,
+            self.tuple.x * other.tuple.y - self.tuple.y * other.tuple.x,
+        )
+    }
+
+    /// Reflects this vector around `normal`, which is expected to be normalized.
+    pub fn reflect(&self, normal: &Vector) -> Vector {
+        self - &(normal * (2.0 * self.dot(normal)))
+    }
+
+    /// Refracts this vector through a surface with the given `normal`, where
+    /// `eta_ratio` is the ratio of the refractive indices `n1 / n2` on the
+    /// incoming and outgoing side. Both vectors are expected to be normalized,
+    /// with `self` pointing towards the surface.
+    ///
+    /// Returns `None` on total internal reflection.
+    pub fn refract(&self, normal: &Vector, eta_ratio: CoordValue) -> Option<Vector> {
+        let cos_i = -self.dot(normal);
+        let sin2_t = eta_ratio * eta_ratio * (1.0 - cos_i * cos_i);
+        if sin2_t > 1.0 {
+            return None;
         }
+        let cos_t = CoordValue::sqrt(1.0 - sin2_t);
+        Some(self * eta_ratio + normal * (eta_ratio * cos_i - cos_t))
     }
 
     pub fn format(&self) -> String {
@@ -120,15 +136,76 @@ impl Vector {
     }
 }
 
+#[cfg(feature = "serde")]
+impl From<crate::tuple::Coordinates> for Vector {
+    fn from(c: crate::tuple::Coordinates) -> Vector {
+        Vector::from_array(c.as_array())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<Vector> for crate::tuple::Coordinates {
+    fn from(v: Vector) -> crate::tuple::Coordinates {
+        crate::tuple::Coordinates::new(v.tuple.x, v.tuple.y, v.tuple.z)
+    }
+}
+
 impl PartialEq for Vector {
     fn eq(&self, other: &Vector) -> bool {
         self.tuple.eq(&other.tuple)
     }
 }
 
+impl ops::Add<&Vector> for &Vector {
+    type Output = Vector;
+
+    fn add(self, other: &Vector) -> Vector {
+        Vector::add(self, other)
+    }
+}
+
+impl ops::Sub<&Vector> for &Vector {
+    type Output = Vector;
+
+    fn sub(self, other: &Vector) -> Vector {
+        Vector::sub(self, other)
+    }
+}
+
+impl ops::Neg for &Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::neg(self)
+    }
+}
+
+impl ops::Mul<CoordValue> for &Vector {
+    type Output = Vector;
+
+    fn mul(self, scalar: CoordValue) -> Vector {
+        self.scalar_mul(scalar)
+    }
+}
+
+impl ops::Div<CoordValue> for &Vector {
+    type Output = Vector;
+
+    fn div(self, scalar: CoordValue) -> Vector {
+        self.scalar_div(scalar)
+    }
+}
+
+forward_binop!(Vector, Add, add, AddAssign, add_assign, Vector, Vector);
+forward_binop!(Vector, Sub, sub, SubAssign, sub_assign, Vector, Vector);
+forward_neg!(Vector);
+forward_scalar_op!(Vector, Mul, mul, MulAssign, mul_assign);
+forward_scalar_op!(Vector, Div, div, DivAssign, div_assign);
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::assert_approx_eq;
     const EPSILON: CoordValue = 0.0000001;
 
     #[test]
@@ -259,7 +336,7 @@ mod tests {
     #[test]
     fn test_magnitude() {
         let v1 = Vector::new(1.0, 2.0, 3.0);
-        assert!(v1.magnitude() - f32::sqrt(14.0) < EPSILON);
+        assert!(v1.magnitude() - CoordValue::sqrt(14.0) < EPSILON);
     }
 
     #[test]
@@ -302,9 +379,9 @@ mod tests {
     fn test_normalize() {
         let v1 = Vector::new(1.0, 2.0, 3.0);
         let v2 = v1.normalize();
-        assert!(v2.as_tuple().x - 1.0 / f32::sqrt(14.0) < EPSILON);
-        assert!(v2.as_tuple().y - 2.0 / f32::sqrt(14.0) < EPSILON);
-        assert!(v2.as_tuple().z - 3.0 / f32::sqrt(14.0) < EPSILON);
+        assert!(v2.as_tuple().x - 1.0 / CoordValue::sqrt(14.0) < EPSILON);
+        assert!(v2.as_tuple().y - 2.0 / CoordValue::sqrt(14.0) < EPSILON);
+        assert!(v2.as_tuple().z - 3.0 / CoordValue::sqrt(14.0) < EPSILON);
     }
 
     #[test]
@@ -334,4 +411,110 @@ mod tests {
         assert_eq!(v4.tuple.y, -2.0);
         assert_eq!(v4.tuple.z, 1.0);
     }
+
+    #[test]
+    fn test_w_is_0() {
+        let v = Vector::new(1.0, 2.0, 3.0);
+        assert_eq!(v.tuple.w, 0.0);
+        assert!(v.as_tuple().is_vector());
+        assert_eq!(v.normalize().tuple.w, 0.0);
+        assert_eq!(v.cross(&Vector::new(2.0, 3.0, 4.0)).tuple.w, 0.0);
+    }
+
+    #[test]
+    fn test_round_trip_through_tuple() {
+        let v = Vector::new(1.0, -2.0, 3.0);
+        assert_eq!(Vector::from(v.as_tuple()), v);
+        assert_eq!(Vector::from(Tuple::point(1.0, -2.0, 3.0)), v);
+    }
+
+    #[test]
+    fn test_operators() {
+        let v1 = Vector::new(1.0, 2.0, 3.0);
+        let v2 = Vector::new(4.0, 5.0, 6.0);
+        assert_eq!(&v1 + &v2, Vector::new(5.0, 7.0, 9.0));
+        assert_eq!(&v1 - &v2, Vector::new(-3.0, -3.0, -3.0));
+        assert_eq!(-&v1, Vector::new(-1.0, -2.0, -3.0));
+        assert_eq!(&v1 * 2.0, Vector::new(2.0, 4.0, 6.0));
+        assert_eq!(&v1 / 2.0, Vector::new(0.5, 1.0, 1.5));
+        assert_eq!(v1.clone() + v2.clone(), Vector::new(5.0, 7.0, 9.0));
+        assert_eq!(v1.clone() - &v2, Vector::new(-3.0, -3.0, -3.0));
+        assert_eq!(v1 * 2.0, Vector::new(2.0, 4.0, 6.0));
+        assert_eq!(-v2, Vector::new(-4.0, -5.0, -6.0));
+    }
+
+    #[test]
+    fn test_assign_operators() {
+        let mut v = Vector::new(1.0, 2.0, 3.0);
+        v += Vector::new(1.0, 1.0, 1.0);
+        assert_eq!(v, Vector::new(2.0, 3.0, 4.0));
+        v -= &Vector::new(2.0, 2.0, 2.0);
+        assert_eq!(v, Vector::new(0.0, 1.0, 2.0));
+        v *= 4.0;
+        assert_eq!(v, Vector::new(0.0, 4.0, 8.0));
+        v /= 2.0;
+        assert_eq!(v, Vector::new(0.0, 2.0, 4.0));
+    }
+
+    #[test]
+    fn test_reflect_at_45_degrees() {
+        let v = Vector::new(1.0, -1.0, 0.0);
+        let n = Vector::new(0.0, 1.0, 0.0);
+        assert_eq!(v.reflect(&n), Vector::new(1.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn test_reflect_off_slanted_surface() {
+        let v = Vector::new(0.0, -1.0, 0.0);
+        let half = CoordValue::sqrt(2.0) / 2.0;
+        let n = Vector::new(half, half, 0.0);
+        assert_approx_eq!(v.reflect(&n), Vector::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_refract_with_equal_indices_keeps_direction() {
+        let v = Vector::new(1.0, -1.0, 0.0).normalize();
+        let n = Vector::new(0.0, 1.0, 0.0);
+        assert_approx_eq!(v.refract(&n, 1.0).unwrap(), v);
+    }
+
+    #[test]
+    fn test_refract_head_on_is_not_bent() {
+        let v = Vector::new(0.0, -1.0, 0.0);
+        let n = Vector::new(0.0, 1.0, 0.0);
+        assert_approx_eq!(v.refract(&n, 1.0 / 1.5).unwrap(), v);
+    }
+
+    #[test]
+    fn test_refract_bends_towards_normal_into_denser_medium() {
+        let v = Vector::new(1.0, -1.0, 0.0).normalize();
+        let n = Vector::new(0.0, 1.0, 0.0);
+        let eta: CoordValue = 1.0 / 1.5;
+        let t = v.refract(&n, eta).unwrap();
+        let sin_i = CoordValue::sqrt(2.0) / 2.0;
+        let sin_t = eta * sin_i;
+        assert_approx_eq!(
+            t,
+            Vector::new(sin_t, -CoordValue::sqrt(1.0 - sin_t * sin_t), 0.0)
+        );
+        assert_approx_eq!(t.magnitude(), 1.0);
+    }
+
+    #[test]
+    fn test_refract_returns_none_on_total_internal_reflection() {
+        let v = Vector::new(1.0, -1.0, 0.0).normalize();
+        let n = Vector::new(0.0, 1.0, 0.0);
+        assert_eq!(v.refract(&n, 1.5), None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        let v = Vector::new(1.0, -2.5, 3.0);
+        let json = serde_json::to_string(&v).unwrap();
+        assert_eq!(json, r#"{"x":1.0,"y":-2.5,"z":3.0}"#);
+        let back: Vector = serde_json::from_str(&json).unwrap();
+        assert_eq!(back, v);
+        assert!(back.as_tuple().is_vector());
+    }
 }
@@ -0,0 +1,835 @@
+use crate::approx::EPSILON;
+use crate::color::Color;
+use crate::light::PointLight;
+use crate::lighting::lighting;
+use crate::normal::Normal;
+use crate::point::Point;
+use crate::ray::{Intersection, Intersections, Ray};
+use crate::shapes::Shape;
+use crate::tuple::CoordValue;
+use crate::vector::Vector;
+
+/// Distance by which hit points are pushed off their surface before casting
+/// secondary rays from them. Without it, rounding errors (large with `f32`)
+/// put the point slightly below the surface, which then shadows itself and
+/// shows up as "acne".
+pub const SURFACE_EPSILON: CoordValue = EPSILON;
+
+/// How many times rays may bounce off mirrors or pass through transparent
+/// surfaces by default, see [`World::max_depth`].
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
+/// Everything about a hit that shading needs, computed once.
+#[derive(Debug)]
+pub struct Computations<'a> {
+    pub t: CoordValue,
+    pub object: &'a dyn Shape,
+    pub point: Point,
+    /// `point` moved [`SURFACE_EPSILON`] along the normal, above the surface.
+    pub over_point: Point,
+    /// `point` moved [`SURFACE_EPSILON`] against the normal, below the
+    /// surface, where refracted rays start.
+    pub under_point: Point,
+    /// Direction towards the eye.
+    pub eyev: Vector,
+    /// Surface normal, facing the eye.
+    pub normalv: Normal,
+    /// Direction of the ray reflected off the surface.
+    pub reflectv: Vector,
+    /// Whether the hit is on the inside of the object, in which case
+    /// `normalv` has been flipped.
+    pub inside: bool,
+    /// Refractive index of the material the ray comes from.
+    pub n1: CoordValue,
+    /// Refractive index of the material the ray goes into.
+    pub n2: CoordValue,
+}
+
+impl<'a> Computations<'a> {
+    /// Prepares `hit`, one of the intersections `xs` of `ray`. The other
+    /// intersections tell which objects the ray is inside of, and hence the
+    /// refractive indices on both sides of the surface.
+    pub fn prepare(
+        hit: &Intersection<'a, dyn Shape>,
+        ray: &Ray,
+        xs: &Intersections<'a, dyn Shape>,
+    ) -> Computations<'a> {
+        let point = ray.position(hit.t);
+        let eyev = -&ray.direction;
+        let normalv = hit.object.normal_at_hit(&point, hit);
+        let inside = normalv.dot(&eyev) < 0.0;
+        let normalv = if inside { -normalv } else { normalv };
+        let offset = &normalv.as_vector() * SURFACE_EPSILON;
+        let over_point = &point + &offset;
+        let under_point = &point - &offset;
+        let reflectv = ray.direction.reflect(&normalv.as_vector());
+        let (n1, n2) = refractive_indices(hit, xs);
+        Computations {
+            t: hit.t,
+            object: hit.object,
+            point,
+            over_point,
+            under_point,
+            eyev,
+            normalv,
+            reflectv,
+            inside,
+            n1,
+            n2,
+        }
+    }
+
+    /// Fraction of the light reflected by the surface, following Schlick's
+    /// approximation of the Fresnel equations.
+    pub fn schlick(&self) -> CoordValue {
+        let mut cos = self.normalv.dot(&self.eyev);
+        if self.n1 > self.n2 {
+            let n = self.n1 / self.n2;
+            let sin2_t = n * n * (1.0 - cos * cos);
+            if sin2_t > 1.0 {
+                // Total internal reflection
+                return 1.0;
+            }
+            cos = CoordValue::sqrt(1.0 - sin2_t);
+        }
+        let r0 = ((self.n1 - self.n2) / (self.n1 + self.n2)).powi(2);
+        r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+    }
+}
+
+/// Refractive indices on both sides of the surface at `hit`, found by walking
+/// `xs` up to it while keeping track of the objects the ray is inside of.
+/// Outside of every object is vacuum.
+fn refractive_indices(
+    hit: &Intersection<'_, dyn Shape>,
+    xs: &Intersections<'_, dyn Shape>,
+) -> (CoordValue, CoordValue) {
+    let index_of = |containers: &[&dyn Shape]| {
+        containers
+            .last()
+            .map_or(1.0, |object| object.material().refractive_index)
+    };
+
+    let mut containers: Vec<&dyn Shape> = Vec::new();
+    for i in xs {
+        let is_hit = i == hit;
+        let n1 = index_of(&containers);
+        match containers
+            .iter()
+            .position(|object| std::ptr::addr_eq(*object, i.object))
+        {
+            Some(position) => {
+                containers.remove(position);
+            }
+            None => containers.push(i.object),
+        }
+        if is_hit {
+            return (n1, index_of(&containers));
+        }
+    }
+    (1.0, 1.0)
+}
+
+/// A scene: the objects to render and the lights shining on them.
+#[derive(Debug)]
+pub struct World {
+    pub objects: Vec<Box<dyn Shape>>,
+    pub lights: Vec<PointLight>,
+    /// How many times a ray may be reflected or refracted before being
+    /// considered black.
+    pub max_depth: usize,
+}
+
+impl World {
+    pub fn new() -> World {
+        World {
+            objects: Vec::new(),
+            lights: Vec::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    pub fn add_object<S: Shape + 'static>(&mut self, object: S) {
+        self.objects.push(Box::new(object));
+    }
+
+    pub fn add_light(&mut self, light: PointLight) {
+        self.lights.push(light);
+    }
+
+    /// Intersections of `ray` with every object of the world, sorted by `t`.
+    pub fn intersect(&self, ray: &Ray) -> Intersections<'_, dyn Shape> {
+        self.objects
+            .iter()
+            .flat_map(|object| object.intersect(ray))
+            .collect()
+    }
+
+    /// Color seen along `ray`, black when it hits nothing.
+    pub fn color_at(&self, ray: &Ray) -> Color {
+        self.color_at_depth(ray, self.max_depth)
+    }
+
+    /// Color seen along `ray`, allowing `remaining` more reflections or
+    /// refractions.
+    pub fn color_at_depth(&self, ray: &Ray, remaining: usize) -> Color {
+        let xs = self.intersect(ray);
+        match xs.hit() {
+            Some(hit) => self.shade_hit(&Computations::prepare(hit, ray, &xs), remaining),
+            None => Color::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Color at a prepared hit, adding up the contribution of every light and
+    /// of the reflected and refracted rays.
+    pub fn shade_hit(&self, comps: &Computations, remaining: usize) -> Color {
+        let surface = self
+            .lights
+            .iter()
+            .fold(Color::new(0.0, 0.0, 0.0), |color, light| {
+                let in_shadow = self.is_shadowed(light, &comps.over_point);
+                &color
+                    + &lighting(
+                        comps.object.material(),
+                        comps.object,
+                        light,
+                        &comps.over_point,
+                        &comps.eyev,
+                        &comps.normalv,
+                        in_shadow,
+                    )
+            });
+        let reflected = self.reflected_color(comps, remaining);
+        let refracted = self.refracted_color(comps, remaining);
+
+        let material = comps.object.material();
+        if material.reflective > 0.0 && material.transparency > 0.0 {
+            let reflectance = comps.schlick();
+            &(&surface + &(&reflected * reflectance)) + &(&refracted * (1.0 - reflectance))
+        } else {
+            &(&surface + &reflected) + &refracted
+        }
+    }
+
+    /// Color brought by the ray reflected at a hit, black on non-reflective
+    /// surfaces or when no more bounces are allowed.
+    pub fn reflected_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let reflective = comps.object.material().reflective;
+        if reflective == 0.0 || remaining == 0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let ray = Ray::new(comps.over_point.clone(), comps.reflectv.clone());
+        &self.color_at_depth(&ray, remaining - 1) * reflective
+    }
+
+    /// Color brought by the ray refracted at a hit, black on opaque surfaces,
+    /// on total internal reflection or when no more bounces are allowed.
+    pub fn refracted_color(&self, comps: &Computations, remaining: usize) -> Color {
+        let transparency = comps.object.material().transparency;
+        if transparency == 0.0 || remaining == 0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        match (-&comps.eyev).refract(&comps.normalv.as_vector(), comps.n1 / comps.n2) {
+            Some(direction) => {
+                let ray = Ray::new(comps.under_point.clone(), direction);
+                &self.color_at_depth(&ray, remaining - 1) * transparency
+            }
+            None => Color::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    /// Whether an object lies between `point` and `light`.
+    pub fn is_shadowed(&self, light: &PointLight, point: &Point) -> bool {
+        let v = &light.position - point;
+        let distance = v.magnitude();
+        let ray = Ray::new(point.clone(), v.normalize());
+        match self.intersect(&ray).hit() {
+            Some(hit) => hit.t < distance,
+            None => false,
+        }
+    }
+}
+
+impl Default for World {
+    fn default() -> Self {
+        World::new()
+    }
+}
+
+/// The usual two concentric spheres lit from the top left front, shared by
+/// the world and camera tests.
+#[cfg(test)]
+pub(crate) fn default_world() -> World {
+    use crate::material::Material;
+    use crate::point::Point;
+    use crate::shapes::Sphere;
+    use crate::transform::scaling;
+
+    let mut outer = Sphere::new();
+    outer.set_material(Material {
+        color: Color::new(0.8, 1.0, 0.6),
+        diffuse: 0.7,
+        specular: 0.2,
+        ..Material::default()
+    });
+    let mut inner = Sphere::new();
+    inner.set_transform(scaling(0.5, 0.5, 0.5)).unwrap();
+
+    let mut world = World::new();
+    world.add_light(PointLight::new(
+        Point::new(-10.0, 10.0, -10.0),
+        Color::new(1.0, 1.0, 1.0),
+    ));
+    world.add_object(outer);
+    world.add_object(inner);
+    world
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::material::Material;
+    use crate::patterns::TestPattern;
+    use crate::shapes::{Plane, Sphere};
+    use crate::transform::{scaling, translation};
+    use crate::tuple::consts::{FRAC_1_SQRT_2, SQRT_2};
+    use crate::{assert_approx_eq, assert_approx_ne};
+
+    #[test]
+    fn test_new_world_is_empty() {
+        let w = World::new();
+        assert!(w.objects.is_empty());
+        assert!(w.lights.is_empty());
+    }
+
+    #[test]
+    fn test_intersect() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let ts: Vec<CoordValue> = w.intersect(&r).iter().map(|i| i.t).collect();
+        assert_eq!(ts, vec![4.0, 4.5, 5.5, 6.0]);
+    }
+
+    #[test]
+    fn test_color_when_ray_misses() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 1.0, 0.0));
+        assert_eq!(w.color_at(&r), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_color_when_ray_hits() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        assert_approx_eq!(w.color_at(&r), Color::new(0.38066, 0.47583, 0.2855));
+    }
+
+    #[test]
+    fn test_color_when_hit_is_inside() {
+        let mut w = default_world();
+        w.lights = vec![PointLight::new(
+            Point::new(0.0, 0.25, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )];
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        assert_approx_eq!(w.color_at(&r), Color::new(0.90498, 0.90498, 0.90498));
+    }
+
+    #[test]
+    fn test_color_with_intersection_behind_ray() {
+        let mut w = default_world();
+        for object in w.objects.iter_mut() {
+            object.properties_mut().material.ambient = 1.0;
+        }
+        let r = Ray::new(Point::new(0.0, 0.0, 0.75), Vector::new(0.0, 0.0, -1.0));
+        assert_approx_eq!(w.color_at(&r), w.objects[1].material().color);
+    }
+
+    #[test]
+    fn test_lights_add_up() {
+        let mut w = World::new();
+        w.add_object(Sphere::new());
+        let light = PointLight::new(Point::new(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        w.add_light(light.clone());
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let single = w.color_at(&r);
+        w.add_light(light);
+        assert_approx_eq!(w.color_at(&r), &single * 2.0);
+    }
+
+    #[test]
+    fn test_prepare_computations() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let shape = Sphere::new();
+        let xs = Intersections::from_vec(vec![Intersection::new(4.0, &shape as &dyn Shape)]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(comps.t, 4.0);
+        assert!(std::ptr::addr_eq(comps.object, &shape));
+        assert_eq!(comps.point, Point::new(0.0, 0.0, -1.0));
+        assert_eq!(comps.eyev, Vector::new(0.0, 0.0, -1.0));
+        assert_eq!(comps.normalv, Normal::new(0.0, 0.0, -1.0));
+        assert!(!comps.inside);
+    }
+
+    #[test]
+    fn test_prepare_computations_inside() {
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let shape = Sphere::new();
+        let xs = Intersections::from_vec(vec![Intersection::new(1.0, &shape as &dyn Shape)]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(comps.point, Point::new(0.0, 0.0, 1.0));
+        assert_eq!(comps.eyev, Vector::new(0.0, 0.0, -1.0));
+        assert!(comps.inside);
+        // Flipped to face the eye
+        assert_eq!(comps.normalv, Normal::new(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn test_over_point_is_above_surface() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut shape = Sphere::new();
+        shape.set_transform(translation(0.0, 0.0, 1.0)).unwrap();
+        let xs = Intersections::from_vec(vec![Intersection::new(5.0, &shape as &dyn Shape)]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert!(comps.over_point.z() < -SURFACE_EPSILON / 2.0);
+        assert!(comps.point.z() > comps.over_point.z());
+    }
+
+    #[test]
+    fn test_surface_epsilon_survives_rounding() {
+        // The offset must stay visible next to coordinates of a typical scene
+        // size, whatever the precision of CoordValue
+        for coordinate in [1.0, 10.0, 100.0] {
+            let p: CoordValue = coordinate;
+            assert!(p + SURFACE_EPSILON > p);
+            assert!(p - SURFACE_EPSILON < p);
+        }
+    }
+
+    #[test]
+    fn test_no_shadow_when_nothing_is_collinear_with_point_and_light() {
+        let w = default_world();
+        assert!(!w.is_shadowed(&w.lights[0], &Point::new(0.0, 10.0, 0.0)));
+    }
+
+    #[test]
+    fn test_shadow_when_object_is_between_point_and_light() {
+        let w = default_world();
+        assert!(w.is_shadowed(&w.lights[0], &Point::new(10.0, -10.0, 10.0)));
+    }
+
+    #[test]
+    fn test_no_shadow_when_object_is_behind_light() {
+        let w = default_world();
+        assert!(!w.is_shadowed(&w.lights[0], &Point::new(-20.0, 20.0, -20.0)));
+    }
+
+    #[test]
+    fn test_no_shadow_when_object_is_behind_point() {
+        let w = default_world();
+        assert!(!w.is_shadowed(&w.lights[0], &Point::new(-2.0, 2.0, -2.0)));
+    }
+
+    #[test]
+    fn test_shade_hit_in_shadow() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
+        w.add_object(Sphere::new());
+        let mut s2 = Sphere::new();
+        s2.set_transform(translation(0.0, 0.0, 10.0)).unwrap();
+        w.add_object(s2);
+        let r = Ray::new(Point::new(0.0, 0.0, 5.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::from_vec(vec![Intersection::new(4.0, w.objects[1].as_ref())]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_approx_eq!(w.shade_hit(&comps, w.max_depth), Color::new(0.1, 0.1, 0.1));
+    }
+
+    #[test]
+    fn test_lit_surface_does_not_shadow_itself() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(
+            Point::new(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
+        let mut s = Sphere::new();
+        s.set_transform(translation(0.3, 0.2, 0.0)).unwrap();
+        w.add_object(s);
+        // Grazing rays across the lit half of the sphere
+        for k in 0..20 {
+            let y = k as CoordValue * 0.05 - 0.3;
+            let r = Ray::new(Point::new(0.3, y, -5.0), Vector::new(0.0, 0.0, 1.0));
+            let xs = w.intersect(&r);
+            let comps = Computations::prepare(xs.hit().unwrap(), &r, &xs);
+            assert!(!w.is_shadowed(&w.lights[0], &comps.over_point));
+            assert_approx_ne!(w.shade_hit(&comps, w.max_depth), Color::new(0.1, 0.1, 0.1));
+        }
+    }
+
+    fn glass_sphere() -> Sphere {
+        let mut s = Sphere::new();
+        s.set_material(Material {
+            transparency: 1.0,
+            refractive_index: 1.5,
+            ..Material::default()
+        });
+        s
+    }
+
+    fn floor(material: Material) -> Plane {
+        let mut floor = Plane::new();
+        floor.set_transform(translation(0.0, -1.0, 0.0)).unwrap();
+        floor.set_material(material);
+        floor
+    }
+
+    #[test]
+    fn test_new_world_default_max_depth() {
+        assert_eq!(World::new().max_depth, DEFAULT_MAX_DEPTH);
+        assert_eq!(World::default().max_depth, DEFAULT_MAX_DEPTH);
+    }
+
+    #[test]
+    fn test_prepare_reflection_vector() {
+        let shape = Plane::new();
+        let r = Ray::new(
+            Point::new(0.0, 1.0, -1.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let xs = Intersections::from_vec(vec![Intersection::new(SQRT_2, &shape as &dyn Shape)]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_approx_eq!(
+            comps.reflectv,
+            Vector::new(0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+        );
+    }
+
+    #[test]
+    fn test_prepare_n1_n2_at_various_intersections() {
+        let mut a = glass_sphere();
+        a.set_transform(scaling(2.0, 2.0, 2.0)).unwrap();
+        a.properties_mut().material.refractive_index = 1.5;
+        let mut b = glass_sphere();
+        b.set_transform(translation(0.0, 0.0, -0.25)).unwrap();
+        b.properties_mut().material.refractive_index = 2.0;
+        let mut c = glass_sphere();
+        c.set_transform(translation(0.0, 0.0, 0.25)).unwrap();
+        c.properties_mut().material.refractive_index = 2.5;
+        let (a, b, c): (&dyn Shape, &dyn Shape, &dyn Shape) = (&a, &b, &c);
+
+        let r = Ray::new(Point::new(0.0, 0.0, -4.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::from_vec(vec![
+            Intersection::new(2.0, a),
+            Intersection::new(2.75, b),
+            Intersection::new(3.25, c),
+            Intersection::new(4.75, b),
+            Intersection::new(5.25, c),
+            Intersection::new(6.0, a),
+        ]);
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+        for (i, (n1, n2)) in xs.iter().zip(expected) {
+            let comps = Computations::prepare(i, &r, &xs);
+            assert_eq!((comps.n1, comps.n2), (n1, n2));
+        }
+    }
+
+    #[test]
+    fn test_under_point_is_below_surface() {
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let mut shape = glass_sphere();
+        shape.set_transform(translation(0.0, 0.0, 1.0)).unwrap();
+        let xs = Intersections::from_vec(vec![Intersection::new(5.0, &shape as &dyn Shape)]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert!(comps.under_point.z() > SURFACE_EPSILON / 2.0);
+        assert!(comps.point.z() < comps.under_point.z());
+    }
+
+    #[test]
+    fn test_reflected_color_for_nonreflective_material() {
+        let mut w = default_world();
+        w.objects[1].properties_mut().material.ambient = 1.0;
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::from_vec(vec![Intersection::new(1.0, w.objects[1].as_ref())]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(
+            w.reflected_color(&comps, w.max_depth),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_reflected_color_for_reflective_material() {
+        let mut w = default_world();
+        w.add_object(floor(Material {
+            reflective: 0.5,
+            ..Material::default()
+        }));
+        let r = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let xs = Intersections::from_vec(vec![Intersection::new(SQRT_2, w.objects[2].as_ref())]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_approx_eq!(
+            w.reflected_color(&comps, w.max_depth),
+            Color::new(0.19032, 0.2379, 0.14274),
+            0.001
+        );
+    }
+
+    #[test]
+    fn test_shade_hit_with_reflective_material() {
+        let mut w = default_world();
+        w.add_object(floor(Material {
+            reflective: 0.5,
+            ..Material::default()
+        }));
+        let r = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let xs = Intersections::from_vec(vec![Intersection::new(SQRT_2, w.objects[2].as_ref())]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_approx_eq!(
+            w.shade_hit(&comps, w.max_depth),
+            Color::new(0.87677, 0.92436, 0.82918),
+            0.001
+        );
+    }
+
+    #[test]
+    fn test_mutually_reflective_surfaces_terminate() {
+        let mut w = World::new();
+        w.add_light(PointLight::new(
+            Point::new(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
+        let mirror = Material {
+            reflective: 1.0,
+            ..Material::default()
+        };
+        w.add_object(floor(mirror.clone()));
+        let mut upper = Plane::new();
+        upper.set_transform(translation(0.0, 1.0, 0.0)).unwrap();
+        upper.set_material(mirror);
+        w.add_object(upper);
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        // Each bounce adds some light, until the depth limit stops the ray
+        let bounced = w.color_at(&r);
+        w.max_depth = 0;
+        let surface_only = w.color_at(&r);
+        assert!(bounced.red().is_finite());
+        assert!(bounced.red() > surface_only.red());
+    }
+
+    #[test]
+    fn test_reflected_color_at_maximum_depth() {
+        let mut w = default_world();
+        w.add_object(floor(Material {
+            reflective: 0.5,
+            ..Material::default()
+        }));
+        let r = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let xs = Intersections::from_vec(vec![Intersection::new(SQRT_2, w.objects[2].as_ref())]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(w.reflected_color(&comps, 0), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_refracted_color_with_opaque_surface() {
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let shape = w.objects[0].as_ref();
+        let xs = Intersections::from_vec(vec![
+            Intersection::new(4.0, shape),
+            Intersection::new(6.0, shape),
+        ]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(
+            w.refracted_color(&comps, w.max_depth),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_refracted_color_at_maximum_depth() {
+        let mut w = default_world();
+        let material = &mut w.objects[0].properties_mut().material;
+        material.transparency = 1.0;
+        material.refractive_index = 1.5;
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let shape = w.objects[0].as_ref();
+        let xs = Intersections::from_vec(vec![
+            Intersection::new(4.0, shape),
+            Intersection::new(6.0, shape),
+        ]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_eq!(w.refracted_color(&comps, 0), Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_refracted_color_under_total_internal_reflection() {
+        let mut w = default_world();
+        let material = &mut w.objects[0].properties_mut().material;
+        material.transparency = 1.0;
+        material.refractive_index = 1.5;
+        let r = Ray::new(
+            Point::new(0.0, 0.0, FRAC_1_SQRT_2),
+            Vector::new(0.0, 1.0, 0.0),
+        );
+        let shape = w.objects[0].as_ref();
+        let xs = Intersections::from_vec(vec![
+            Intersection::new(-FRAC_1_SQRT_2, shape),
+            Intersection::new(FRAC_1_SQRT_2, shape),
+        ]);
+        // Inside the sphere, so the second intersection is the one to look at
+        let comps = Computations::prepare(&xs[1], &r, &xs);
+        assert_eq!(
+            w.refracted_color(&comps, w.max_depth),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn test_refracted_color_with_refracted_ray() {
+        let mut w = default_world();
+        let a = &mut w.objects[0].properties_mut().material;
+        a.ambient = 1.0;
+        a.set_pattern(TestPattern::new());
+        let b = &mut w.objects[1].properties_mut().material;
+        b.transparency = 1.0;
+        b.refractive_index = 1.5;
+        let r = Ray::new(Point::new(0.0, 0.0, 0.1), Vector::new(0.0, 1.0, 0.0));
+        let (a, b) = (w.objects[0].as_ref(), w.objects[1].as_ref());
+        let xs = Intersections::from_vec(vec![
+            Intersection::new(-0.9899, a),
+            Intersection::new(-0.4899, b),
+            Intersection::new(0.4899, b),
+            Intersection::new(0.9899, a),
+        ]);
+        let comps = Computations::prepare(&xs[2], &r, &xs);
+        assert_approx_eq!(
+            w.refracted_color(&comps, w.max_depth),
+            Color::new(0.0, 0.99888, 0.04725),
+            0.001
+        );
+    }
+
+    #[test]
+    fn test_shade_hit_with_transparent_material() {
+        let mut w = default_world();
+        w.add_object(floor(Material {
+            transparency: 0.5,
+            refractive_index: 1.5,
+            ..Material::default()
+        }));
+        let mut ball = Sphere::new();
+        ball.set_transform(translation(0.0, -3.5, -0.5)).unwrap();
+        ball.set_material(Material {
+            color: Color::new(1.0, 0.0, 0.0),
+            ambient: 0.5,
+            ..Material::default()
+        });
+        w.add_object(ball);
+        let r = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let xs = Intersections::from_vec(vec![Intersection::new(SQRT_2, w.objects[2].as_ref())]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_approx_eq!(
+            w.shade_hit(&comps, w.max_depth),
+            Color::new(0.93642, 0.68642, 0.68642),
+            0.001
+        );
+    }
+
+    #[test]
+    fn test_schlick_under_total_internal_reflection() {
+        let shape = glass_sphere();
+        let r = Ray::new(
+            Point::new(0.0, 0.0, FRAC_1_SQRT_2),
+            Vector::new(0.0, 1.0, 0.0),
+        );
+        let xs = Intersections::from_vec(vec![
+            Intersection::new(-FRAC_1_SQRT_2, &shape as &dyn Shape),
+            Intersection::new(FRAC_1_SQRT_2, &shape as &dyn Shape),
+        ]);
+        let comps = Computations::prepare(&xs[1], &r, &xs);
+        assert_eq!(comps.schlick(), 1.0);
+    }
+
+    #[test]
+    fn test_schlick_with_perpendicular_viewing_angle() {
+        let shape = glass_sphere();
+        let r = Ray::new(Point::new(0.0, 0.0, 0.0), Vector::new(0.0, 1.0, 0.0));
+        let xs = Intersections::from_vec(vec![
+            Intersection::new(-1.0, &shape as &dyn Shape),
+            Intersection::new(1.0, &shape as &dyn Shape),
+        ]);
+        let comps = Computations::prepare(&xs[1], &r, &xs);
+        assert_approx_eq!(comps.schlick(), 0.04);
+    }
+
+    #[test]
+    fn test_schlick_with_small_angle_and_n2_greater_than_n1() {
+        let shape = glass_sphere();
+        let r = Ray::new(Point::new(0.0, 0.99, -2.0), Vector::new(0.0, 0.0, 1.0));
+        let xs = Intersections::from_vec(vec![Intersection::new(1.8589, &shape as &dyn Shape)]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_approx_eq!(comps.schlick(), 0.48873, 0.001);
+    }
+
+    #[test]
+    fn test_shade_hit_with_reflective_transparent_material() {
+        let mut w = default_world();
+        w.add_object(floor(Material {
+            reflective: 0.5,
+            transparency: 0.5,
+            refractive_index: 1.5,
+            ..Material::default()
+        }));
+        let mut ball = Sphere::new();
+        ball.set_transform(translation(0.0, -3.5, -0.5)).unwrap();
+        ball.set_material(Material {
+            color: Color::new(1.0, 0.0, 0.0),
+            ambient: 0.5,
+            ..Material::default()
+        });
+        w.add_object(ball);
+        let r = Ray::new(
+            Point::new(0.0, 0.0, -3.0),
+            Vector::new(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let xs = Intersections::from_vec(vec![Intersection::new(SQRT_2, w.objects[2].as_ref())]);
+        let comps = Computations::prepare(&xs[0], &r, &xs);
+        assert_approx_eq!(
+            w.shade_hit(&comps, w.max_depth),
+            Color::new(0.93391, 0.69643, 0.69243),
+            0.001
+        );
+    }
+
+    #[test]
+    fn test_world_can_be_shared_between_threads() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<World>();
+        assert_send_sync::<Material>();
+        let w = default_world();
+        let r = Ray::new(Point::new(0.0, 0.0, -5.0), Vector::new(0.0, 0.0, 1.0));
+        let color = std::thread::scope(|scope| scope.spawn(|| w.color_at(&r)).join().unwrap());
+        assert_eq!(color, w.color_at(&r));
+    }
+}